@@ -0,0 +1,210 @@
+use std::{convert::Infallible, env, fs, sync::Arc};
+
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, State},
+    http::{header, request::Parts, Method, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::problem::Kind;
+
+/// the routes that can be used without a token, the probes are requested by
+/// the deployment
+const PUBLIC_PATHS: [&str; 4] = ["/api.json", "/docs", "/healthz", "/readyz"];
+
+/// Scope is what a token is allowed to do, `read` tokens can only use `GET`
+/// routes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Scope {
+    Read,
+    Write,
+}
+
+impl Scope {
+    fn parse(scope: &str) -> Option<Scope> {
+        match scope {
+            "read" => Some(Scope::Read),
+            "write" => Some(Scope::Write),
+            _ => None,
+        }
+    }
+
+    fn required(method: &Method) -> Scope {
+        match *method {
+            Method::GET | Method::HEAD | Method::OPTIONS => Scope::Read,
+            _ => Scope::Write,
+        }
+    }
+}
+
+/// Grant is what a token allows and who it belongs to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Grant {
+    scope: Scope,
+    user: Option<String>,
+}
+
+/// Tokens are the bearer tokens accepted by the server with their scopes.
+/// They're empty if authentication is disabled with `FRIDAY_AUTH=off`.
+#[derive(Clone, Debug, Default)]
+pub struct Tokens(Arc<Vec<(String, Grant)>>);
+
+impl Tokens {
+    /// Reads the tokens from `FRIDAY_TOKENS` and the file in
+    /// `FRIDAY_TOKENS_FILE`. Entries look like `read=<token>` or
+    /// `write=<token>`, they're comma separated in the env var and one per
+    /// line in the file. A token can belong to a user like
+    /// `write:tobi=<token>`, tasks added with it are attributed to them.
+    ///
+    /// Panics if no tokens are configured and authentication isn't disabled
+    /// with `FRIDAY_AUTH=off`, so that the API isn't exposed by accident.
+    pub fn from_env() -> Tokens {
+        let mut entries = crate::split_param(env::var("FRIDAY_TOKENS").ok());
+        if let Ok(path) = env::var("FRIDAY_TOKENS_FILE") {
+            let content = fs::read_to_string(&path)
+                .unwrap_or_else(|e| panic!("FRIDAY_TOKENS_FILE {path} can't be read: {e}"));
+            entries.extend(
+                content
+                    .lines()
+                    .map(str::trim)
+                    .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                    .map(String::from),
+            );
+        }
+        let tokens = Tokens::parse(&entries)
+            .expect("tokens should be configured like read=token or write=token");
+        match (tokens.is_empty(), env::var("FRIDAY_AUTH").as_deref() == Ok("off")) {
+            (true, false) => panic!(
+                "tokens should be configured in FRIDAY_TOKENS or FRIDAY_TOKENS_FILE, set \
+                 FRIDAY_AUTH=off to serve the API without authentication"
+            ),
+            (false, true) => panic!("FRIDAY_AUTH=off disables authentication, tokens aren't used"),
+            _ => tokens,
+        }
+    }
+
+    fn parse(entries: &[String]) -> Option<Tokens> {
+        let tokens = entries
+            .iter()
+            .map(|entry| {
+                let (grant, token) = entry.split_once('=')?;
+                let (scope, user) = match grant.split_once(':') {
+                    Some((scope, user)) if !user.trim().is_empty() => {
+                        (scope, Some(user.trim().to_string()))
+                    }
+                    Some(_) => return None,
+                    None => (grant, None),
+                };
+                let token = token.trim();
+                let grant = Grant { scope: Scope::parse(scope.trim())?, user };
+                (!token.is_empty()).then_some((token.to_string(), grant))
+            })
+            .collect::<Option<_>>()?;
+        Some(Tokens(Arc::new(tokens)))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // every token is compared to not leak which ones exist through the
+    // response time.
+    fn grant(&self, token: &str) -> Option<&Grant> {
+        self.0.iter().fold(None, |found, (t, grant)| {
+            if constant_time_eq(t.as_bytes(), token.as_bytes()) {
+                Some(grant)
+            } else {
+                found
+            }
+        })
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Author is the user of the token of a request. It's empty if the token
+/// doesn't belong to a user or authentication is disabled.
+#[derive(Clone, Debug, Default)]
+pub struct Author(pub Option<String>);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for Author {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts.extensions.get::<Author>().cloned().unwrap_or_default())
+    }
+}
+
+// the author isn't part of the request as seen by clients
+impl aide::OperationInput for Author {}
+
+/// Rejects requests without a valid bearer token with 401 and requests that
+/// need more than the scope of the token with 403. The user of the token is
+/// passed on to the handlers as [`Author`].
+pub async fn authorize<B>(
+    State(tokens): State<Tokens>,
+    mut req: Request<B>,
+    next: Next<B>,
+) -> Response {
+    if tokens.is_empty() || PUBLIC_PATHS.contains(&req.uri().path()) {
+        return next.run(req).await;
+    }
+
+    let token = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    let Some(grant) = token.and_then(|token| tokens.grant(token.trim())) else {
+        return (
+            [(header::WWW_AUTHENTICATE, "Bearer")],
+            Kind::Unauthorized.response("a valid bearer token is required"),
+        )
+            .into_response();
+    };
+    if grant.scope < Scope::required(req.method()) {
+        return Kind::Forbidden
+            .response(format!("the token can't be used for {} requests", req.method()))
+            .into_response();
+    }
+    req.extensions_mut().insert(Author(grant.user.clone()));
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(entries: &[&str]) -> Vec<String> {
+        entries.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn parse_tokens() {
+        let tokens = Tokens::parse(&entries(&["read=abc", " write = def "])).unwrap();
+        assert_eq!(tokens.grant("abc"), Some(&Grant { scope: Scope::Read, user: None }));
+        assert_eq!(tokens.grant("def"), Some(&Grant { scope: Scope::Write, user: None }));
+        assert_eq!(tokens.grant("ab"), None);
+        assert_eq!(tokens.grant(""), None);
+    }
+
+    #[test]
+    fn parse_tokens_with_user() {
+        let tokens = Tokens::parse(&entries(&["write:tobi=abc"])).unwrap();
+        let user = Some("tobi".to_string());
+        assert_eq!(tokens.grant("abc"), Some(&Grant { scope: Scope::Write, user }));
+    }
+
+    #[test]
+    fn parse_invalid_tokens() {
+        assert!(Tokens::parse(&entries(&["admin=abc"])).is_none());
+        assert!(Tokens::parse(&entries(&["abc"])).is_none());
+        assert!(Tokens::parse(&entries(&["read="])).is_none());
+        assert!(Tokens::parse(&entries(&["write:=abc"])).is_none());
+    }
+}
@@ -0,0 +1,381 @@
+use std::cmp;
+
+use aide::transform::{TransformOperation, TransformResponse};
+use axum::Json;
+use friday_client::{Candidate, ListResponse, Problem};
+use friday_core::{fuzzy_match, Match, Section, Task};
+use indexmap::IndexMap;
+
+use crate::{page::SelectedTask, problem::Kind};
+
+// The functions here document the routes in the OpenAPI spec, they're passed
+// to the `*_with` routing functions.
+
+pub fn get_tasks(op: TransformOperation) -> TransformOperation {
+    op.summary("List all tasks")
+        .description(
+            "Returns all sections of the default list with their tasks in the order of the \
+             file. With filters only the sections with matching tasks are returned. The tasks \
+             are paged through across sections, the `X-Total-Count` header holds the number \
+             of all tasks and the `X-Next-Cursor` header the cursor of the next page.",
+        )
+        .tag("tasks")
+        .response_with::<200, Json<IndexMap<Section, Vec<SelectedTask>>>, _>(paged_sections)
+        .response_with::<400, Json<Problem>, _>(invalid_page)
+}
+
+pub fn post_task(op: TransformOperation) -> TransformOperation {
+    op.summary("Create a task")
+        .description(
+            "Adds a task to the end of the default section of the default list. Relative due \
+             dates like `due:friday` are resolved to absolute ones. The task is attributed to \
+             the user of the token. The `Location` header of the response points to the \
+             created task.",
+        )
+        .tag("tasks")
+        .response_with::<201, Json<Task>, _>(|res| {
+            res.description("the created task").example(example_tasks().remove(0))
+        })
+        .response_with::<400, Json<Problem>, _>(invalid_task)
+        .response_with::<413, Json<Problem>, _>(too_long)
+}
+
+pub fn get_task(op: TransformOperation) -> TransformOperation {
+    op.summary("Get a task")
+        .description(
+            "Returns a single task of a section of the default list. The id is derived from the \
+             text of the task, so it changes whenever the task is edited.",
+        )
+        .tag("tasks")
+        .response_with::<200, Json<Task>, _>(|res| res.example(example_tasks().remove(0)))
+        .response_with::<404, Json<Problem>, _>(task_not_found)
+}
+
+pub fn get_tasks_in_section(op: TransformOperation) -> TransformOperation {
+    op.summary("List the tasks of a section")
+        .description(
+            "Returns the tasks of a section of the default list matching the filters. Long \
+             lists are split into pages, the next one is requested with the `next_cursor` of \
+             the response. The cursor points after the last task of the page, so tasks added \
+             or removed in between are neither skipped nor returned twice. It's outdated once \
+             that task is removed.",
+        )
+        .tag("tasks")
+        .response_with::<200, Json<ListResponse<SelectedTask>>, _>(paged_tasks)
+        .response_with::<400, Json<Problem>, _>(invalid_page)
+        .response_with::<404, Json<Problem>, _>(|res| {
+            res.description("the section doesn't exist")
+                .example(Kind::SectionNotFound.problem("section todo not found"))
+        })
+}
+
+pub fn post_tasks(op: TransformOperation) -> TransformOperation {
+    op.summary("Add a task")
+        .description(
+            "Adds a task to the end of a section of the default list. Relative due dates like \
+             `due:friday` are resolved to absolute ones. Returns all sections afterwards.",
+        )
+        .tag("tasks")
+        .response_with::<200, Json<IndexMap<Section, Vec<Task>>>, _>(|res| {
+            res.example(example_sections())
+        })
+        .response_with::<400, Json<Problem>, _>(invalid_task)
+        .response_with::<413, Json<Problem>, _>(too_long)
+}
+
+pub fn delete_tasks(op: TransformOperation) -> TransformOperation {
+    op.summary("Delete a task")
+        .description(
+            "Deletes the task containing the pattern from a section of the default list. \
+             Returns all sections afterwards.",
+        )
+        .tag("tasks")
+        .response_with::<200, Json<IndexMap<Section, Vec<Task>>>, _>(after_delete)
+        .response_with::<404, Json<Problem>, _>(|res| {
+            res.description("no task matches")
+                .example(Kind::TaskNotFound.problem("no task matching deps found"))
+        })
+        .response_with::<409, Json<Problem>, _>(ambiguous)
+}
+
+pub fn delete_task(op: TransformOperation) -> TransformOperation {
+    op.summary("Delete a task by id")
+        .description(
+            "Deletes a single task of a section of the default list. Returns all sections \
+             afterwards.",
+        )
+        .tag("tasks")
+        .response_with::<200, Json<IndexMap<Section, Vec<Task>>>, _>(after_delete)
+        .response_with::<404, Json<Problem>, _>(task_not_found)
+}
+
+pub fn batch(op: TransformOperation) -> TransformOperation {
+    op.summary("Run a batch of operations")
+        .description(
+            "Adds, removes, moves and completes tasks of the default list in the given order. \
+             The batch is atomic, if one operation fails none of them is applied and the \
+             problem names the failed operation. Returns all sections afterwards.",
+        )
+        .tag("tasks")
+        .response_with::<200, Json<IndexMap<Section, Vec<Task>>>, _>(|res| {
+            res.example(example_sections())
+        })
+        .response_with::<404, Json<Problem>, _>(|res| {
+            res.description("an operation didn't match any task").example(
+                Kind::TaskNotFound.problem("operation 2 failed: no task matching deps found"),
+            )
+        })
+}
+
+pub fn search(op: TransformOperation) -> TransformOperation {
+    op.summary("Search tasks")
+        .description(
+            "Searches all sections of the default list for tasks fuzzily matching the query, \
+             the best matches come first. The positions of the matched characters can be used \
+             for highlighting.",
+        )
+        .tag("search")
+        .response_with::<200, Json<ListResponse<Match>>, _>(matches)
+}
+
+pub fn get_lists(op: TransformOperation) -> TransformOperation {
+    op.summary("List all lists")
+        .description(
+            "Returns the names of all lists. The `default` list is the one served under \
+             `/tasks`.",
+        )
+        .tag("lists")
+        .response_with::<200, Json<ListResponse<String>>, _>(|res| {
+            res.example(ListResponse::from(vec!["default".to_string(), "team".to_string()]))
+        })
+}
+
+// The routes of a list work like the ones of the default list, they're only
+// also not found if the list doesn't exist.
+
+pub fn get_list_tasks(op: TransformOperation) -> TransformOperation {
+    op.summary("List all tasks of a list")
+        .description(
+            "Returns all sections of the list with their tasks in the order of the file. \
+             Filters and paging work like for `GET /tasks`, the `X-Total-Count` header holds \
+             the number of all tasks and the `X-Next-Cursor` header the cursor of the next \
+             page.",
+        )
+        .tag("lists")
+        .response_with::<200, Json<IndexMap<Section, Vec<SelectedTask>>>, _>(paged_sections)
+        .response_with::<400, Json<Problem>, _>(invalid_page)
+        .response_with::<404, Json<Problem>, _>(list_not_found("the list doesn't exist"))
+}
+
+pub fn get_list_tasks_in_section(op: TransformOperation) -> TransformOperation {
+    op.summary("List the tasks of a section of a list")
+        .description(
+            "Returns the tasks of a section of the list matching the filters. Long lists are \
+             split into pages like for `GET /tasks/{section}`, the next one is requested with \
+             the `next_cursor` of the response.",
+        )
+        .tag("lists")
+        .response_with::<200, Json<ListResponse<SelectedTask>>, _>(paged_tasks)
+        .response_with::<400, Json<Problem>, _>(invalid_page)
+        .response_with::<404, Json<Problem>, _>(list_not_found(
+            "the list or the section doesn't exist",
+        ))
+}
+
+pub fn get_list_task(op: TransformOperation) -> TransformOperation {
+    op.summary("Get a task of a list")
+        .description(
+            "Returns a single task of a section of the list. The id is derived from the text of \
+             the task, so it changes whenever the task is edited.",
+        )
+        .tag("lists")
+        .response_with::<200, Json<Task>, _>(|res| res.example(example_tasks().remove(0)))
+        .response_with::<404, Json<Problem>, _>(list_not_found(
+            "the list, the section or the task doesn't exist",
+        ))
+}
+
+pub fn delete_list_task(op: TransformOperation) -> TransformOperation {
+    op.summary("Delete a task of a list by id")
+        .description(
+            "Deletes a single task of a section of the list. Returns all sections of the list \
+             afterwards.",
+        )
+        .tag("lists")
+        .response_with::<200, Json<IndexMap<Section, Vec<Task>>>, _>(after_delete)
+        .response_with::<404, Json<Problem>, _>(list_not_found(
+            "the list, the section or the task doesn't exist",
+        ))
+}
+
+pub fn post_list_tasks(op: TransformOperation) -> TransformOperation {
+    op.summary("Add a task to a list")
+        .description(
+            "Adds a task to the end of a section of the list. Relative due dates like \
+             `due:friday` are resolved to absolute ones. The task is attributed to the user of \
+             the token. Returns all sections of the list afterwards.",
+        )
+        .tag("lists")
+        .response_with::<200, Json<IndexMap<Section, Vec<Task>>>, _>(|res| {
+            res.example(example_sections())
+        })
+        .response_with::<400, Json<Problem>, _>(invalid_task)
+        .response_with::<404, Json<Problem>, _>(list_not_found("the list doesn't exist"))
+        .response_with::<413, Json<Problem>, _>(too_long)
+}
+
+pub fn delete_list_tasks(op: TransformOperation) -> TransformOperation {
+    op.summary("Delete a task from a list")
+        .description(
+            "Deletes the task containing the pattern from a section of the list. Returns all \
+             sections of the list afterwards.",
+        )
+        .tag("lists")
+        .response_with::<200, Json<IndexMap<Section, Vec<Task>>>, _>(after_delete)
+        .response_with::<404, Json<Problem>, _>(list_not_found(
+            "the list doesn't exist or no task matches",
+        ))
+        .response_with::<409, Json<Problem>, _>(ambiguous)
+}
+
+pub fn batch_list(op: TransformOperation) -> TransformOperation {
+    op.summary("Run a batch of operations on a list")
+        .description(
+            "Adds, removes, moves and completes tasks of the list in the given order. The batch \
+             is atomic, if one operation fails none of them is applied and the problem names \
+             the failed operation. Returns all sections of the list afterwards.",
+        )
+        .tag("lists")
+        .response_with::<200, Json<IndexMap<Section, Vec<Task>>>, _>(|res| {
+            res.example(example_sections())
+        })
+        .response_with::<404, Json<Problem>, _>(list_not_found(
+            "the list doesn't exist or an operation didn't match any task",
+        ))
+}
+
+pub fn search_list(op: TransformOperation) -> TransformOperation {
+    op.summary("Search tasks of a list")
+        .description(
+            "Searches all sections of the list for tasks fuzzily matching the query, the best \
+             matches come first. The positions of the matched characters can be used for \
+             highlighting.",
+        )
+        .tag("lists")
+        .response_with::<200, Json<ListResponse<Match>>, _>(matches)
+        .response_with::<404, Json<Problem>, _>(list_not_found("the list doesn't exist"))
+}
+
+fn paged_sections(
+    res: TransformResponse<IndexMap<Section, Vec<SelectedTask>>>,
+) -> TransformResponse<IndexMap<Section, Vec<SelectedTask>>> {
+    res.description("the sections, tasks only include the selected fields").example(
+        example_sections()
+            .into_iter()
+            .map(|(section, tasks)| (section, tasks.iter().map(Into::into).collect()))
+            .collect::<IndexMap<Section, Vec<SelectedTask>>>(),
+    )
+}
+
+fn paged_tasks(
+    res: TransformResponse<ListResponse<SelectedTask>>,
+) -> TransformResponse<ListResponse<SelectedTask>> {
+    let examples = example_tasks();
+    let tasks: Vec<SelectedTask> = examples.iter().map(Into::into).collect();
+    res.description("the tasks, they only include the selected fields").example(ListResponse {
+        next_cursor: Some(format!("2.{}", examples[1].id)),
+        total: 3,
+        ..tasks.into()
+    })
+}
+
+fn after_delete(
+    res: TransformResponse<IndexMap<Section, Vec<Task>>>,
+) -> TransformResponse<IndexMap<Section, Vec<Task>>> {
+    res.description("the sections without the deleted task").example(example_sections())
+}
+
+fn matches(res: TransformResponse<ListResponse<Match>>) -> TransformResponse<ListResponse<Match>> {
+    res.description(
+        "the matching tasks with their score and the char indices of the matched characters \
+         in the text, the example is the result for `deps`",
+    )
+    .example(example_matches("deps"))
+}
+
+fn invalid_task(res: TransformResponse<Problem>) -> TransformResponse<Problem> {
+    res.description("the task is empty or has an invalid due date")
+        .example(Kind::InvalidInput.problem("Invalid argument: invalid due date someday"))
+}
+
+fn task_not_found(res: TransformResponse<Problem>) -> TransformResponse<Problem> {
+    res.description("the section or task doesn't exist")
+        .example(Kind::TaskNotFound.problem("task e40c292c not found"))
+}
+
+fn ambiguous(res: TransformResponse<Problem>) -> TransformResponse<Problem> {
+    res.description(
+        "more than one task matches, they're listed as candidates. To select one of them its \
+         id can be passed as the pattern.",
+    )
+    .example(Problem {
+        candidates: ["update deps", "review deps PR"]
+            .iter()
+            .map(|t| {
+                let task: Task = t.parse().unwrap();
+                Candidate { section: "todo".to_string(), id: task.id, task: task.text }
+            })
+            .collect(),
+        ..Kind::AmbiguousMatch.problem("pattern deps matches 2 tasks")
+    })
+}
+
+// list_not_found documents the 404 of the routes of a list, the example is the
+// one for an unknown list since only these routes return it.
+fn list_not_found(
+    description: &'static str,
+) -> impl FnOnce(TransformResponse<Problem>) -> TransformResponse<Problem> {
+    move |res| {
+        res.description(description).example(Kind::ListNotFound.problem("list team not found"))
+    }
+}
+
+fn too_long(res: TransformResponse<Problem>) -> TransformResponse<Problem> {
+    res.description("the task exceeds the maximum length")
+        .example(Kind::TooLarge.problem("task exceeds the maximum length of 1000 characters"))
+}
+
+fn invalid_page(res: TransformResponse<Problem>) -> TransformResponse<Problem> {
+    res.description("the cursor, limit or fields are invalid or the cursor is outdated")
+        .example(Kind::InvalidInput.problem("unknown field title"))
+}
+
+fn example_tasks() -> Vec<Task> {
+    [
+        "update deps due:2026-10-23 every:week #infra <!-- author:tobi \
+         created:2026-10-16T08:00:00Z -->",
+        "(A) review the PR of @anna assignee:tobi",
+    ]
+    .iter()
+    .map(|t| t.parse().unwrap())
+    .collect()
+}
+
+fn example_sections() -> IndexMap<Section, Vec<Task>> {
+    IndexMap::from([(Section::Custom("todo".to_string()), example_tasks())])
+}
+
+// example_matches searches the example tasks like the manager does, so the
+// positions are real.
+fn example_matches(query: &str) -> ListResponse<Match> {
+    let mut matches: Vec<Match> = example_sections()
+        .into_iter()
+        .flat_map(|(section, tasks)| tasks.into_iter().map(move |task| (section.clone(), task)))
+        .filter_map(|(section, task)| {
+            let (score, positions) = fuzzy_match(&task.text, query)?;
+            Some(Match { section, task, score, positions })
+        })
+        .collect();
+    matches.sort_by_key(|m| cmp::Reverse(m.score));
+    matches.into()
+}
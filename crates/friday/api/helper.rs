@@ -1,17 +1,83 @@
+use std::{env, panic, str::FromStr, time::Duration};
+
 use aide::openapi::{Info, OpenApi};
-use tokio::signal;
-use tracing::Level;
-use tracing_subscriber::prelude::*;
+use axum::{
+    body::{Body, BoxBody},
+    http::{Request, Response},
+};
+use tokio::{signal, task};
+use tracing::Span;
+use tracing_subscriber::{filter::Targets, fmt::format::FmtSpan, prelude::*};
+
+/// the filter used without `FRIDAY_LOG`, spans of the manager and the repo are
+/// enabled with e.g. `info,friday_core=debug`
+const DEFAULT_FILTER: &str = "info";
+
+/// the header requests are identified by in logs and responses
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// LogFormat is the format of the logs set with `FRIDAY_LOG_FORMAT`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum LogFormat {
+    /// a line per event
+    Text,
+    /// multiple lines per event for reading them during development
+    Pretty,
+    /// a JSON object per line for log collectors
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "" | "text" => Ok(LogFormat::Text),
+            "pretty" => Ok(LogFormat::Pretty),
+            "json" => Ok(LogFormat::Json),
+            other => Err(format!("expected text, pretty or json, got {other}")),
+        }
+    }
+}
 
+/// Logs to stdout in the format of `FRIDAY_LOG_FORMAT` with the filter of
+/// `FRIDAY_LOG`, e.g. `info,tower_http=debug`. Spans are logged when they
+/// close, so the duration of requests and file operations is visible.
 pub fn enable_tracing() {
-    // https://stackoverflow.com/questions/74302133/how-to-log-and-filter-requests-with-axum-tokio
-    let filter = tracing_subscriber::filter::Targets::new()
-        .with_target("tower_http::trace::on_response", Level::DEBUG)
-        .with_target("tower_http::trace::on_request", Level::DEBUG)
-        .with_target("tower_http::trace::make_span", Level::DEBUG)
-        .with_default(Level::INFO);
-    let tracing_layer = tracing_subscriber::fmt::layer();
-    tracing_subscriber::registry().with(tracing_layer).with(filter).init();
+    let filter = env::var("FRIDAY_LOG").unwrap_or_default();
+    let filter: Targets = if filter.trim().is_empty() { DEFAULT_FILTER } else { filter.trim() }
+        .parse()
+        .expect("FRIDAY_LOG should be a filter like info,friday_core=debug");
+    let format: LogFormat = env::var("FRIDAY_LOG_FORMAT")
+        .unwrap_or_default()
+        .parse()
+        .expect("FRIDAY_LOG_FORMAT should be text, pretty or json");
+
+    let layer = tracing_subscriber::fmt::layer().with_span_events(FmtSpan::CLOSE);
+    let registry = tracing_subscriber::registry().with(filter);
+    match format {
+        LogFormat::Text => registry.with(layer).init(),
+        LogFormat::Pretty => registry.with(layer.pretty()).init(),
+        LogFormat::Json => registry.with(layer.json()).init(),
+    }
+}
+
+/// Creates the span of a request with the id set by the request id layer, the
+/// status is recorded by [`record_status`].
+pub fn make_span(req: &Request<Body>) -> Span {
+    let request_id =
+        req.headers().get(REQUEST_ID_HEADER).and_then(|id| id.to_str().ok()).unwrap_or_default();
+    tracing::info_span!(
+        "request",
+        method = %req.method(),
+        uri = %req.uri(),
+        request_id,
+        status = tracing::field::Empty
+    )
+}
+
+pub fn record_status(res: &Response<BoxBody>, _latency: Duration, span: &Span) {
+    span.record("status", res.status().as_u16());
 }
 
 pub async fn shutdown_signal() {
@@ -40,7 +106,72 @@ pub async fn shutdown_signal() {
 
 pub fn openapi_spec() -> OpenApi {
     OpenApi {
-        info: Info { description: Some("Friday API".to_string()), ..Info::default() },
+        info: Info {
+            title: "Friday API".to_string(),
+            description: Some(
+                "Manage stuff to do on fridays. Tasks are stored in markdown files, grouped in \
+                 sections and annotated with `#tags`, `@people`, `due:` dates, `every:` \
+                 recurrences and `!1`..`!3` priorities. Errors are returned as RFC 7807 problem \
+                 details. The docs are served at `/docs`.\n\nUnless authentication is disabled \
+                 on the server, requests need a bearer token in the `Authorization` header. \
+                 Tokens with the `read` scope can only be used for `GET` requests, `write` \
+                 tokens for all of them. Requests without a valid token are rejected with 401, \
+                 requests exceeding the scope of the token with 403. `/api.json`, `/docs` \
+                 and the `/healthz` and `/readyz` probes are always public. Tasks added with \
+                 a token that belongs to a user are attributed to them.\n\nClients exceeding \
+                 the rate limit of the server get 429 with a `Retry-After` header. Bodies and \
+                 tasks exceeding the configured sizes are rejected with 413, requests taking \
+                 too long with 408.\n\nEvery response has an `X-Request-Id` header to find the \
+                 request in the logs of the server. Ids sent by clients are kept."
+                    .to_string(),
+            ),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            ..Info::default()
+        },
         ..OpenApi::default()
     }
 }
+
+// matchit, the router of axum, treats a `:` anywhere in a route as the start of
+// a path parameter. Routes of custom methods like `/tasks:batch` are therefore
+// registered with an encoded colon, requests are rewritten before routing to
+// match them and the spec lists them with the colon again.
+pub fn encode_custom_method<B>(mut req: Request<B>) -> Request<B> {
+    let uri = req.uri();
+    let Some((path, last)) = uri.path().rsplit_once('/') else {
+        return req;
+    };
+    if !last.contains(':') {
+        return req;
+    }
+
+    let mut encoded = format!("{path}/{}", last.replace(':', "%3A"));
+    if let Some(query) = uri.query() {
+        encoded = format!("{encoded}?{query}");
+    }
+    if let Ok(encoded) = encoded.parse() {
+        *req.uri_mut() = encoded;
+    }
+    req
+}
+
+pub fn decode_custom_methods(api: &mut OpenApi) {
+    if let Some(paths) = api.paths.as_mut() {
+        paths.paths =
+            paths.paths.drain(..).map(|(path, item)| (path.replace("%3A", ":"), item)).collect();
+    }
+}
+
+/// Runs the synchronous file access of a handler on the blocking threads, so
+/// that it doesn't hold up the workers serving other requests and their
+/// timeouts. The span of the request is kept, so that the logs of the repo
+/// have its id. Once started, the access is finished even if the request
+/// times out, so files are never left half written.
+pub async fn blocking<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> T {
+    let span = Span::current();
+    match task::spawn_blocking(move || span.in_scope(f)).await {
+        Ok(res) => res,
+        // panics are passed on as if the handler ran on the worker
+        Err(e) => panic::resume_unwind(e.into_panic()),
+    }
+}
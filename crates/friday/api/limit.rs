@@ -0,0 +1,201 @@
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Debug,
+    net::{IpAddr, SocketAddr},
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::{ConnectInfo, State},
+    http::{header, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::problem::Kind;
+
+/// the maximum number of clients whose buckets are kept. Beyond it the ones
+/// that haven't sent requests for a minute are forgotten, their buckets are
+/// full again anyway. If all are active, the least recently seen one is.
+const MAX_CLIENTS: usize = 1024;
+
+/// Limits keep single clients from overloading the server or flooding the
+/// shared files.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Limits {
+    /// the requests per minute of a client, 0 disables rate limiting
+    pub rate: u32,
+    /// the maximum size of request bodies in bytes
+    pub max_body_size: usize,
+    /// the maximum duration of a request including reading the body
+    pub timeout: Duration,
+    /// the maximum number of characters of added tasks
+    pub max_task_len: usize,
+}
+
+impl Limits {
+    /// Reads the limits from `FRIDAY_RATE_LIMIT`, `FRIDAY_MAX_BODY_SIZE`,
+    /// `FRIDAY_REQUEST_TIMEOUT` in seconds and `FRIDAY_MAX_TASK_LENGTH`.
+    pub fn from_env() -> Limits {
+        Limits {
+            rate: env_or("FRIDAY_RATE_LIMIT", 300),
+            max_body_size: env_or("FRIDAY_MAX_BODY_SIZE", 64 * 1024),
+            timeout: Duration::from_secs(env_or("FRIDAY_REQUEST_TIMEOUT", 10)),
+            max_task_len: env_or("FRIDAY_MAX_TASK_LENGTH", 1000),
+        }
+    }
+}
+
+fn env_or<T: FromStr>(key: &str, default: T) -> T
+where
+    T::Err: Debug,
+{
+    match env::var(key) {
+        Ok(value) if !value.trim().is_empty() => {
+            value.trim().parse().unwrap_or_else(|e| panic!("{key} {value} is invalid: {e:?}"))
+        }
+        _ => default,
+    }
+}
+
+/// Limiter enforces the [`Limits`] of requests. Clients are told apart by
+/// their IP address.
+#[derive(Clone, Debug)]
+pub struct Limiter {
+    limits: Limits,
+    buckets: Arc<Mutex<HashMap<IpAddr, Bucket>>>,
+}
+
+/// Bucket is a token bucket holding up to `rate` tokens. It's refilled
+/// continuously within a minute and every request takes a token.
+#[derive(Clone, Copy, Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Limiter {
+    pub fn new(limits: Limits) -> Limiter {
+        Limiter { limits, buckets: Arc::default() }
+    }
+
+    // take takes a token from the bucket of the client. If it's empty, the
+    // duration until the next token is available is returned.
+    fn take(&self, client: IpAddr, now: Instant) -> Result<(), Duration> {
+        if self.limits.rate == 0 {
+            return Ok(());
+        }
+        let rate = f64::from(self.limits.rate);
+
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() >= MAX_CLIENTS && !buckets.contains_key(&client) {
+            buckets.retain(|_, b| now.duration_since(b.updated) < Duration::from_secs(60));
+            let stalest = buckets.iter().min_by_key(|(_, b)| b.updated).map(|(c, _)| *c);
+            if let Some(stalest) = stalest.filter(|_| buckets.len() >= MAX_CLIENTS) {
+                buckets.remove(&stalest);
+            }
+        }
+        let bucket = buckets.entry(client).or_insert(Bucket { tokens: rate, updated: now });
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate / 60.0).min(rate);
+        bucket.updated = now;
+        if bucket.tokens < 1.0 {
+            return Err(Duration::from_secs_f64((1.0 - bucket.tokens) * 60.0 / rate));
+        }
+        bucket.tokens -= 1.0;
+        Ok(())
+    }
+}
+
+/// Rejects requests of clients exceeding the rate limit with 429 and requests
+/// with a body that is too large with 413. Requests taking longer than the
+/// timeout are aborted with 408. Bodies without a `Content-Length` are capped
+/// by the `DefaultBodyLimit` of the router.
+pub async fn limit<B>(State(limiter): State<Limiter>, req: Request<B>, next: Next<B>) -> Response {
+    let client = req.extensions().get::<ConnectInfo<SocketAddr>>().map(|c| c.0.ip());
+    if let Some(Err(retry_after)) = client.map(|client| limiter.take(client, Instant::now())) {
+        // rounded up, so clients don't retry too early
+        let retry_after = (retry_after.as_secs() + 1).to_string();
+        return (
+            [(header::RETRY_AFTER, retry_after)],
+            Kind::TooManyRequests.response("the rate limit is exceeded, retry later"),
+        )
+            .into_response();
+    }
+
+    let content_length = req
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<usize>().ok());
+    let max_body_size = limiter.limits.max_body_size;
+    if content_length.map_or(false, |len| len > max_body_size) {
+        return Kind::TooLarge
+            .response(format!("the body exceeds the maximum size of {max_body_size} bytes"))
+            .into_response();
+    }
+
+    // the request is dropped when the timeout fires, so e.g. a task whose body
+    // is still being read is never added. The handlers access the files on
+    // the blocking threads, see `blocking`, so they can't delay the timeout.
+    let timeout = limiter.limits.timeout;
+    match tokio::time::timeout(timeout, next.run(req)).await {
+        Ok(res) => res,
+        Err(_) => Kind::Timeout
+            .response(format!("the request took longer than {}s", timeout.as_secs()))
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(rate: u32) -> Limiter {
+        Limiter::new(Limits {
+            rate,
+            max_body_size: 1024,
+            timeout: Duration::from_secs(1),
+            max_task_len: 100,
+        })
+    }
+
+    #[test]
+    fn take_limits_clients_separately() {
+        let limiter = limiter(2);
+        let now = Instant::now();
+        let (a, b) = (IpAddr::from([127, 0, 0, 1]), IpAddr::from([127, 0, 0, 2]));
+        assert!(limiter.take(a, now).is_ok());
+        assert!(limiter.take(a, now).is_ok());
+        assert_eq!(limiter.take(a, now), Err(Duration::from_secs(30)));
+        assert!(limiter.take(b, now).is_ok());
+
+        // a token is refilled every 30 seconds
+        assert!(limiter.take(a, now + Duration::from_secs(30)).is_ok());
+        assert!(limiter.take(a, now + Duration::from_secs(30)).is_err());
+    }
+
+    #[test]
+    fn take_forgets_the_least_recently_seen_clients() {
+        let limiter = limiter(1);
+        let now = Instant::now();
+        let first = IpAddr::from([10, 0, 0, 0]);
+        assert!(limiter.take(first, now).is_ok());
+        for i in 1..=u16::try_from(MAX_CLIENTS).unwrap() {
+            let [a, b] = i.to_be_bytes();
+            assert!(limiter.take(IpAddr::from([10, 0, a, b]), now + Duration::from_secs(1)).is_ok());
+        }
+        assert_eq!(limiter.buckets.lock().unwrap().len(), MAX_CLIENTS);
+        assert!(!limiter.buckets.lock().unwrap().contains_key(&first));
+    }
+
+    #[test]
+    fn take_is_unlimited_without_rate() {
+        let limiter = limiter(0);
+        let now = Instant::now();
+        assert!((0..100).all(|_| limiter.take(IpAddr::from([127, 0, 0, 1]), now).is_ok()));
+    }
+}
@@ -1,124 +1,582 @@
 #![warn(clippy::pedantic)]
 
+mod auth;
+mod docs;
 mod helper;
-use std::sync::{Arc, RwLock};
+mod limit;
+mod metrics;
+mod page;
+mod problem;
+mod tls;
+use std::{
+    env,
+    net::SocketAddr,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
 
 use aide::{
     axum::{
-        routing::{get, post},
+        routing::{delete_with, get, get_with, post_with},
         ApiRouter,
     },
     openapi::OpenApi,
+    redoc::Redoc,
 };
+use auth::Author;
 use axum::{
-    body::Body,
-    extract::{Path, State},
-    http::{header, StatusCode},
-    response::Response,
+    extract::{self, DefaultBodyLimit, Path, State},
+    http::{header, HeaderMap, HeaderName, StatusCode},
+    middleware,
     Extension,
     Json,
+    ServiceExt,
+};
+use friday_client::{Batch, CreateTask, ListResponse};
+use friday_core::{
+    DefaultManager,
+    DueFilter,
+    FileBacked,
+    Manager,
+    Match,
+    Op,
+    Query,
+    Section,
+    Sort,
+    Task,
 };
-use friday_core::{DefaultManager, FileBacked, Manager, Section};
 use helper::*;
 use indexmap::IndexMap;
+use limit::{Limiter, Limits};
+use metrics::{Metrics, Timed};
+use page::{PageParams, SelectedTask};
+use problem::{to_http_err, ErrorResponse, Kind};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use tower_http::trace::TraceLayer;
+use tls::Tls;
+use tower::util::MapRequest;
+use tower_http::{
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
+    trace::TraceLayer,
+};
 
 type Mngr = Arc<RwLock<dyn Manager + Sync + Send>>;
 
+/// the list served under `/tasks`, it's stored in `FRIDAY_FILE`. Additional
+/// lists are configured with `FRIDAY_LISTS`, e.g.
+/// `team=./team.md,oncall=./oncall.md`
+const DEFAULT_LIST: &str = "default";
+const DEFAULT_FILE: &str = "./testing";
+const DEFAULT_ADDR: &str = "0.0.0.0:3000";
+/// how long open connections are waited for when shutting down with TLS
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Lists holds a manager per named list.
+#[derive(Clone)]
+pub struct Lists(Arc<IndexMap<String, Mngr>>);
+
+impl Lists {
+    fn get(&self, list: &str) -> std::result::Result<&Mngr, ErrorResponse> {
+        self.0
+            .get(list)
+            .ok_or_else(|| Kind::ListNotFound.response(format!("list {list} not found")))
+    }
+}
+
+fn lists_from_env(limits: &Limits, metrics: &Metrics) -> Lists {
+    let manager = |file: String| -> Mngr {
+        let repo = Timed::new(FileBacked::new(file).unwrap(), metrics.clone());
+        Arc::new(RwLock::new(DefaultManager::new(repo).with_max_task_len(limits.max_task_len)))
+    };
+    let mut lists: IndexMap<String, Mngr> = IndexMap::new();
+    let default_file = env::var("FRIDAY_FILE").unwrap_or_else(|_| DEFAULT_FILE.to_string());
+    lists.insert(DEFAULT_LIST.to_string(), manager(default_file));
+    for list in split_param(env::var("FRIDAY_LISTS").ok()) {
+        let (name, file) =
+            list.split_once('=').expect("FRIDAY_LISTS should contain entries like name=file");
+        lists.insert(name.trim().to_string(), manager(file.trim().to_string()));
+    }
+    Lists(Arc::new(lists))
+}
+
 #[tokio::main]
 pub async fn main() {
     enable_tracing();
 
-    let repo = FileBacked::new("./testing").unwrap();
-    let manager = Arc::new(RwLock::new(DefaultManager::new(repo)));
-    let api_router = routes().with_state(manager);
+    // named schemas end up in the components of the spec so that clients can
+    // validate responses against them, see tests/api_spec_test.rs.
+    aide::gen::extract_schemas(true);
+    let limits = Limits::from_env();
+    let metrics = Metrics::default();
+    let api_router = routes().with_state(lists_from_env(&limits, &metrics));
     let mut api = openapi_spec();
 
-    tracing::info!("serving on port 3000");
-    let addr = "0.0.0.0:3000".parse().unwrap();
-    axum::Server::bind(&addr)
-        .serve(
-            api_router
-                .finish_api(&mut api)
-                .layer(Extension(api))
-                .layer(TraceLayer::new_for_http())
-                .into_make_service(),
-        )
-        .with_graceful_shutdown(shutdown_signal())
-        .await
-        .unwrap();
+    let addr = env::var("FRIDAY_ADDR").unwrap_or_else(|_| DEFAULT_ADDR.to_string());
+    tracing::info!("serving on {addr}");
+    let addr: SocketAddr =
+        addr.parse().expect("FRIDAY_ADDR should be a socket address like 0.0.0.0:3000");
+    let tokens = auth::Tokens::from_env();
+    if tokens.is_empty() {
+        tracing::warn!("authentication is disabled, the API can be used by anyone");
+    }
+
+    let router = api_router.finish_api(&mut api);
+    decode_custom_methods(&mut api);
+    let app = router
+        .layer(middleware::from_fn_with_state(tokens, auth::authorize))
+        .layer(middleware::from_fn_with_state(metrics.clone(), metrics::track))
+        .layer(DefaultBodyLimit::max(limits.max_body_size))
+        .layer(Extension(api))
+        .layer(Extension(metrics))
+        .layer(middleware::from_fn_with_state(Limiter::new(limits), limit::limit))
+        .layer(TraceLayer::new_for_http().make_span_with(make_span).on_response(record_status))
+        // ids sent by clients are kept, so requests can be followed across services
+        .layer(PropagateRequestIdLayer::x_request_id())
+        .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid));
+    // the address of the client is used for rate limiting
+    let service = MapRequest::new(app, encode_custom_method)
+        .into_make_service_with_connect_info::<SocketAddr>();
+
+    let Some(tls) = Tls::from_env() else {
+        axum::Server::bind(&addr)
+            .serve(service)
+            .with_graceful_shutdown(shutdown_signal())
+            .await
+            .unwrap();
+        return;
+    };
+    if let Ok(redirect_addr) = env::var("FRIDAY_REDIRECT_ADDR") {
+        let redirect_addr = redirect_addr
+            .parse()
+            .expect("FRIDAY_REDIRECT_ADDR should be a socket address like 0.0.0.0:80");
+        tokio::spawn(tls::redirect(redirect_addr, addr.port()));
+    }
+    let handle = axum_server::Handle::new();
+    let shutdown = handle.clone();
+    tokio::spawn(async move {
+        shutdown_signal().await;
+        shutdown.graceful_shutdown(Some(SHUTDOWN_TIMEOUT));
+    });
+    axum_server::bind_rustls(addr, tls.config().await).handle(handle).serve(service).await.unwrap();
 }
 
-pub fn routes() -> ApiRouter<Mngr> {
+pub fn routes() -> ApiRouter<Lists> {
     ApiRouter::new()
-        .api_route("/tasks", get(handle_get_tasks))
-        .api_route("/tasks/:section", get(handle_get_tasks_in_section))
-        .api_route("/tasks/:section", post(handle_post_tasks))
+        .api_route("/tasks", get_with(handle_get_tasks, docs::get_tasks))
+        .api_route("/tasks", post_with(handle_post_task, docs::post_task))
+        .api_route(
+            "/tasks/:section",
+            get_with(handle_get_tasks_in_section, docs::get_tasks_in_section),
+        )
+        .api_route("/tasks/:section", post_with(handle_post_tasks, docs::post_tasks))
+        .api_route("/tasks/:section", delete_with(handle_delete_tasks, docs::delete_tasks))
+        .api_route("/tasks%3Abatch", post_with(handle_batch, docs::batch))
+        .api_route("/tasks/:section/:id", get_with(handle_get_task, docs::get_task))
+        .api_route("/tasks/:section/:id", delete_with(handle_delete_task, docs::delete_task))
+        .api_route("/lists", get_with(handle_get_lists, docs::get_lists))
+        .api_route("/lists/:list/tasks", get_with(handle_get_list_tasks, docs::get_list_tasks))
+        .api_route(
+            "/lists/:list/tasks/:section",
+            get_with(handle_get_list_tasks_in_section, docs::get_list_tasks_in_section),
+        )
+        .api_route(
+            "/lists/:list/tasks/:section",
+            post_with(handle_post_list_tasks, docs::post_list_tasks),
+        )
         .api_route(
-            "/tasks",
-            // NOTE: wait for this https://github.com/tamasfe/aide/pull/38
-            post(|| async {
-                Response::builder()
-                    .status(StatusCode::PERMANENT_REDIRECT)
-                    .header(header::LOCATION, "/tasks/dump")
-                    .body(Body::empty())
-                    .unwrap()
-            }),
+            "/lists/:list/tasks/:section",
+            delete_with(handle_delete_list_tasks, docs::delete_list_tasks),
         )
+        .api_route(
+            "/lists/:list/tasks/:section/:id",
+            get_with(handle_get_list_task, docs::get_list_task),
+        )
+        .api_route(
+            "/lists/:list/tasks/:section/:id",
+            delete_with(handle_delete_list_task, docs::delete_list_task),
+        )
+        .api_route("/lists/:list/tasks%3Abatch", post_with(handle_batch_list, docs::batch_list))
+        .api_route("/lists/:list/search", get_with(handle_search_list, docs::search_list))
+        .api_route("/search", get_with(handle_search, docs::search))
         .route("/api.json", get(|Extension(api): Extension<OpenApi>| async { Json(api) }))
+        // the redoc assets are compiled into the binary, no CDN is needed
+        .route("/docs", get(Redoc::new("/api.json").with_title("Friday API").axum_handler()))
+        .route("/healthz", get(handle_healthz))
+        .route("/readyz", get(handle_readyz))
+        .route("/metrics", get(handle_metrics))
 }
 
+// the probes and metrics aren't part of the spec since they're used by the
+// deployment and not by clients.
 #[allow(clippy::unused_async)] // required for handler function signature
-async fn handle_get_tasks(State(mngr): State<Mngr>) -> Result<IndexMap<Section, Vec<String>>> {
-    let sections = mngr.read().unwrap().sections().map_err(to_http_err)?;
-    Ok((StatusCode::OK, Json(sections)))
+async fn handle_healthz() -> &'static str {
+    "ok"
+}
+
+// handle_readyz checks that the files of all lists can be read and written.
+async fn handle_readyz(
+    State(lists): State<Lists>,
+) -> std::result::Result<&'static str, ErrorResponse> {
+    blocking(move || {
+        for (name, mngr) in lists.0.iter() {
+            mngr.read().unwrap().check().map_err(|e| {
+                Kind::Unavailable.response(format!("list {name} can't be used: {e}"))
+            })?;
+        }
+        Ok("ok")
+    })
+    .await
+}
+
+async fn handle_metrics(
+    State(lists): State<Lists>,
+    Extension(registry): Extension<Metrics>,
+) -> std::result::Result<([(HeaderName, &'static str); 1], String), ErrorResponse> {
+    let tasks = blocking(move || {
+        let mut tasks: IndexMap<String, IndexMap<Section, usize>> = IndexMap::new();
+        for (name, mngr) in lists.0.iter() {
+            let sections =
+                mngr.read().unwrap().sections(&Query::default()).map_err(to_http_err)?;
+            tasks.insert(name.clone(), sections.into_iter().map(|(s, t)| (s, t.len())).collect());
+        }
+        Ok::<_, ErrorResponse>(tasks)
+    })
+    .await?;
+    Ok(([(header::CONTENT_TYPE, metrics::CONTENT_TYPE)], registry.render(&tasks)))
+}
+
+async fn handle_get_tasks(
+    State(lists): State<Lists>,
+    extract::Query(params): extract::Query<TaskParams>,
+    extract::Query(page): extract::Query<PageParams>,
+) -> PagedSections {
+    let mngr = lists.get(DEFAULT_LIST)?.clone();
+    blocking(move || get_tasks(&mngr, params, &page)).await
+}
+
+async fn handle_post_task(
+    State(lists): State<Lists>,
+    Author(author): Author,
+    Json(input): Json<CreateTask>,
+) -> std::result::Result<(StatusCode, [(HeaderName, String); 1], Json<Task>), ErrorResponse> {
+    let section = Section::default();
+    let mngr = lists.get(DEFAULT_LIST)?.clone();
+    let name = section.to_string();
+    let task = blocking(move || {
+        mngr.write().unwrap().add(&input.task, Some(&name), author.as_deref()).map_err(to_http_err)
+    })
+    .await?;
+    let location = format!("/tasks/{section}/{}", task.id);
+    Ok((StatusCode::CREATED, [(header::LOCATION, location)], Json(task)))
+}
+
+async fn handle_get_task(
+    Path(TaskPath { section, id }): Path<TaskPath>,
+    State(lists): State<Lists>,
+) -> Result<Task> {
+    let mngr = lists.get(DEFAULT_LIST)?.clone();
+    blocking(move || get_task(&mngr, &section, &id)).await
+}
+
+async fn handle_delete_task(
+    Path(TaskPath { section, id }): Path<TaskPath>,
+    State(lists): State<Lists>,
+) -> Result<IndexMap<Section, Vec<Task>>> {
+    let mngr = lists.get(DEFAULT_LIST)?.clone();
+    blocking(move || delete_task(&mngr, &section, &id)).await
 }
 
-#[allow(clippy::unused_async)] // required for handler function signature
 async fn handle_get_tasks_in_section(
     Path(section): Path<Section>,
-    State(mngr): State<Mngr>,
-) -> Result<ListResponse<String>> {
-    let items = mngr.read().unwrap().list(Some(&section.to_string())).map_err(to_http_err)?;
-    Ok((StatusCode::OK, Json(ListResponse { items })))
+    State(lists): State<Lists>,
+    extract::Query(params): extract::Query<TaskParams>,
+    extract::Query(page): extract::Query<PageParams>,
+) -> Result<ListResponse<SelectedTask>> {
+    let mngr = lists.get(DEFAULT_LIST)?.clone();
+    blocking(move || get_tasks_in_section(&mngr, &section, params, &page)).await
 }
 
-#[allow(clippy::unused_async)] // required for handler function signature
 async fn handle_post_tasks(
     Path(section): Path<Section>,
-    State(mngr): State<Mngr>,
+    State(lists): State<Lists>,
+    author: Author,
+    Json(input): Json<CreateTask>,
+) -> Result<IndexMap<Section, Vec<Task>>> {
+    let mngr = lists.get(DEFAULT_LIST)?.clone();
+    blocking(move || post_tasks(&mngr, &section, &author, &input)).await
+}
+
+async fn handle_delete_tasks(
+    Path(section): Path<Section>,
+    State(lists): State<Lists>,
+    extract::Query(params): extract::Query<DeleteParams>,
+) -> Result<IndexMap<Section, Vec<Task>>> {
+    let mngr = lists.get(DEFAULT_LIST)?.clone();
+    blocking(move || delete_tasks(&mngr, &section, &params)).await
+}
+
+#[allow(clippy::unused_async)] // required for handler function signature
+async fn handle_get_lists(State(lists): State<Lists>) -> Result<ListResponse<String>> {
+    let names: Vec<String> = lists.0.keys().cloned().collect();
+    Ok((StatusCode::OK, Json(names.into())))
+}
+
+async fn handle_get_list_tasks(
+    Path(ListPath { list }): Path<ListPath>,
+    State(lists): State<Lists>,
+    extract::Query(params): extract::Query<TaskParams>,
+    extract::Query(page): extract::Query<PageParams>,
+) -> PagedSections {
+    let mngr = lists.get(&list)?.clone();
+    blocking(move || get_tasks(&mngr, params, &page)).await
+}
+
+async fn handle_get_list_tasks_in_section(
+    Path(ListSectionPath { list, section }): Path<ListSectionPath>,
+    State(lists): State<Lists>,
+    extract::Query(params): extract::Query<TaskParams>,
+    extract::Query(page): extract::Query<PageParams>,
+) -> Result<ListResponse<SelectedTask>> {
+    let mngr = lists.get(&list)?.clone();
+    blocking(move || get_tasks_in_section(&mngr, &section, params, &page)).await
+}
+
+async fn handle_get_list_task(
+    Path(ListTaskPath { list, section, id }): Path<ListTaskPath>,
+    State(lists): State<Lists>,
+) -> Result<Task> {
+    let mngr = lists.get(&list)?.clone();
+    blocking(move || get_task(&mngr, &section, &id)).await
+}
+
+async fn handle_delete_list_task(
+    Path(ListTaskPath { list, section, id }): Path<ListTaskPath>,
+    State(lists): State<Lists>,
+) -> Result<IndexMap<Section, Vec<Task>>> {
+    let mngr = lists.get(&list)?.clone();
+    blocking(move || delete_task(&mngr, &section, &id)).await
+}
+
+async fn handle_post_list_tasks(
+    Path(ListSectionPath { list, section }): Path<ListSectionPath>,
+    State(lists): State<Lists>,
+    author: Author,
     Json(input): Json<CreateTask>,
-) -> Result<IndexMap<Section, Vec<String>>> {
-    mngr.write().unwrap().add(&input.task, Some(&section.to_string())).map_err(to_http_err)?;
-    let sections = mngr.read().unwrap().sections().map_err(to_http_err)?;
+) -> Result<IndexMap<Section, Vec<Task>>> {
+    let mngr = lists.get(&list)?.clone();
+    blocking(move || post_tasks(&mngr, &section, &author, &input)).await
+}
+
+async fn handle_delete_list_tasks(
+    Path(ListSectionPath { list, section }): Path<ListSectionPath>,
+    State(lists): State<Lists>,
+    extract::Query(params): extract::Query<DeleteParams>,
+) -> Result<IndexMap<Section, Vec<Task>>> {
+    let mngr = lists.get(&list)?.clone();
+    blocking(move || delete_tasks(&mngr, &section, &params)).await
+}
+
+#[allow(clippy::needless_pass_by_value)] // params are always extracted by value
+fn get_tasks(mngr: &Mngr, params: TaskParams, page: &PageParams) -> PagedSections {
+    let sections = mngr.read().unwrap().sections(&params.into()).map_err(to_http_err)?;
+    let (headers, sections) = page.sections(&sections)?;
+    Ok((StatusCode::OK, headers, Json(sections)))
+}
+
+fn get_tasks_in_section(
+    mngr: &Mngr,
+    section: &Section,
+    params: TaskParams,
+    page: &PageParams,
+) -> Result<ListResponse<SelectedTask>> {
+    let tasks = mngr
+        .read()
+        .unwrap()
+        .list(Some(&section.to_string()), &params.into())
+        .map_err(to_http_err)?;
+    Ok((StatusCode::OK, Json(page.list(&tasks)?)))
+}
+
+fn get_task(mngr: &Mngr, section: &Section, id: &str) -> Result<Task> {
+    Ok((StatusCode::OK, Json(find_task(mngr, section, id)?)))
+}
+
+fn find_task(mngr: &Mngr, section: &Section, id: &str) -> std::result::Result<Task, ErrorResponse> {
+    mngr.read()
+        .unwrap()
+        .list(Some(&section.to_string()), &Query::default())
+        .map_err(to_http_err)?
+        .into_iter()
+        .find(|t| t.id == id)
+        .ok_or_else(|| Kind::TaskNotFound.response(format!("task {id} not found")))
+}
+
+// the text of the task is used as pattern, an exact match is always preferred
+// over others containing it.
+fn delete_task(mngr: &Mngr, section: &Section, id: &str) -> Result<IndexMap<Section, Vec<Task>>> {
+    let task = find_task(mngr, section, id)?;
+    delete_tasks(mngr, section, &DeleteParams { pattern: task.text })
+}
+
+fn post_tasks(
+    mngr: &Mngr,
+    section: &Section,
+    author: &Author,
+    input: &CreateTask,
+) -> Result<IndexMap<Section, Vec<Task>>> {
+    mngr.write()
+        .unwrap()
+        .add(&input.task, Some(&section.to_string()), author.0.as_deref())
+        .map_err(to_http_err)?;
+    let sections = mngr.read().unwrap().sections(&Query::default()).map_err(to_http_err)?;
     Ok((StatusCode::OK, Json(sections)))
 }
 
-type Result<T> = std::result::Result<(StatusCode, Json<T>), (StatusCode, Json<ErrResponse>)>;
+fn delete_tasks(
+    mngr: &Mngr,
+    section: &Section,
+    params: &DeleteParams,
+) -> Result<IndexMap<Section, Vec<Task>>> {
+    mngr.write().unwrap().rm(&params.pattern, Some(&section.to_string())).map_err(to_http_err)?;
+    let sections = mngr.read().unwrap().sections(&Query::default()).map_err(to_http_err)?;
+    Ok((StatusCode::OK, Json(sections)))
+}
+
+async fn handle_batch(
+    State(lists): State<Lists>,
+    author: Author,
+    Json(batch): Json<Batch<Op>>,
+) -> Result<IndexMap<Section, Vec<Task>>> {
+    let mngr = lists.get(DEFAULT_LIST)?.clone();
+    blocking(move || apply(&mngr, &author, &batch)).await
+}
+
+async fn handle_batch_list(
+    Path(ListPath { list }): Path<ListPath>,
+    State(lists): State<Lists>,
+    author: Author,
+    Json(batch): Json<Batch<Op>>,
+) -> Result<IndexMap<Section, Vec<Task>>> {
+    let mngr = lists.get(&list)?.clone();
+    blocking(move || apply(&mngr, &author, &batch)).await
+}
+
+fn apply(mngr: &Mngr, author: &Author, batch: &Batch<Op>) -> Result<IndexMap<Section, Vec<Task>>> {
+    mngr.write().unwrap().apply(&batch.ops, author.0.as_deref()).map_err(to_http_err)?;
+    let sections = mngr.read().unwrap().sections(&Query::default()).map_err(to_http_err)?;
+    Ok((StatusCode::OK, Json(sections)))
+}
+
+async fn handle_search(
+    State(lists): State<Lists>,
+    extract::Query(params): extract::Query<SearchParams>,
+) -> Result<ListResponse<Match>> {
+    let mngr = lists.get(DEFAULT_LIST)?.clone();
+    blocking(move || search(&mngr, &params)).await
+}
+
+async fn handle_search_list(
+    Path(ListPath { list }): Path<ListPath>,
+    State(lists): State<Lists>,
+    extract::Query(params): extract::Query<SearchParams>,
+) -> Result<ListResponse<Match>> {
+    let mngr = lists.get(&list)?.clone();
+    blocking(move || search(&mngr, &params)).await
+}
+
+fn search(mngr: &Mngr, params: &SearchParams) -> Result<ListResponse<Match>> {
+    let matches = mngr.read().unwrap().search(&params.q).map_err(to_http_err)?;
+    Ok((StatusCode::OK, Json(matches.into())))
+}
+
+type Result<T> = std::result::Result<(StatusCode, Json<T>), ErrorResponse>;
+// the sections are paged through like lists, the total and the next cursor are
+// returned as headers though.
+type PagedSections = std::result::Result<
+    (StatusCode, HeaderMap, Json<IndexMap<Section, Vec<SelectedTask>>>),
+    ErrorResponse,
+>;
 
 #[derive(Serialize, Deserialize, JsonSchema)]
-struct CreateTask {
-    task: String,
+struct ListPath {
+    /// the name of the list
+    list: String,
 }
 
 #[derive(Serialize, Deserialize, JsonSchema)]
-struct ListResponse<T> {
-    items: Vec<T>,
+struct ListSectionPath {
+    /// the name of the list
+    list: String,
+    section: Section,
 }
 
 #[derive(Serialize, Deserialize, JsonSchema)]
-struct ErrResponse {
-    message: String,
+struct TaskPath {
+    section: Section,
+    /// the id of the task, it changes whenever the text changes
+    id: String,
 }
 
-#[allow(clippy::needless_pass_by_value)] // easier to use with this signature
-fn to_http_err(e: friday_core::Error) -> (StatusCode, Json<ErrResponse>) {
-    let status = match &e {
-        e if e.to_string().contains("not found") => StatusCode::NOT_FOUND,
-        friday_core::Error::InvalidArgument(_) => StatusCode::BAD_REQUEST,
-        _ => StatusCode::INTERNAL_SERVER_ERROR,
-    };
-    (status, Json(ErrResponse { message: e.to_string() }))
+#[derive(Serialize, Deserialize, JsonSchema)]
+struct ListTaskPath {
+    /// the name of the list
+    list: String,
+    section: Section,
+    /// the id of the task, it changes whenever the text changes
+    id: String,
+}
+
+/// Query parameters to filter and sort tasks.
+/// Multiple tags or people can be passed comma separated, e.g.
+/// `?tag=infra,backend`.
+#[derive(Serialize, Deserialize, JsonSchema)]
+struct TaskParams {
+    /// only tasks tagged with all of the given `#tags`
+    tag: Option<String>,
+    /// only tasks mentioning all of the given `@people`
+    person: Option<String>,
+    /// only tasks assigned to the person with `assignee:<name>`
+    assignee: Option<String>,
+    /// only tasks due until `overdue`, `today`, `this-week`, `next-week` or
+    /// a date like `2026-10-23`
+    due: Option<DueFilter>,
+    /// sort the tasks within each section by `due` date or `priority`
+    sort: Option<Sort>,
+}
+
+impl From<TaskParams> for Query {
+    fn from(params: TaskParams) -> Self {
+        Query {
+            tags: split_param(params.tag),
+            people: split_param(params.person),
+            due: params.due,
+            assignee: params.assignee.filter(|a| !a.trim().is_empty()),
+            sort: params.sort,
+        }
+    }
+}
+
+/// Query parameters to select the task to delete.
+#[derive(Serialize, Deserialize, JsonSchema)]
+struct DeleteParams {
+    /// the task containing the pattern is deleted, it's an error if more
+    /// than one task matches
+    pattern: String,
+}
+
+/// Query parameters for a fuzzy search over all sections.
+#[derive(Serialize, Deserialize, JsonSchema)]
+struct SearchParams {
+    /// the search query, results are ordered by how well they match
+    q: String,
+}
+
+fn split_param(param: Option<String>) -> Vec<String> {
+    param
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect()
 }
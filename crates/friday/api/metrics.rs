@@ -0,0 +1,234 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::{MatchedPath, State},
+    http::{Request, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use friday_core::{Error, Repo, Section};
+use indexmap::IndexMap;
+
+/// the media type of the Prometheus text format
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// the upper bounds of the buckets of duration histograms in seconds
+const BUCKETS: [f64; 11] = [0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0];
+
+/// Metrics collects the request and repo metrics served at `/metrics` in the
+/// Prometheus text format.
+#[derive(Clone, Debug, Default)]
+pub struct Metrics(Arc<Mutex<Registry>>);
+
+#[derive(Debug, Default)]
+struct Registry {
+    /// requests by method, route and status
+    requests: BTreeMap<(String, String, u16), u64>,
+    request_durations: BTreeMap<String, Histogram>,
+    repo_durations: BTreeMap<&'static str, Histogram>,
+}
+
+/// Histogram counts observations in cumulative buckets like Prometheus does.
+#[derive(Debug, Default)]
+struct Histogram {
+    buckets: [u64; BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, duration: Duration) {
+        let secs = duration.as_secs_f64();
+        for (bucket, le) in self.buckets.iter_mut().zip(BUCKETS) {
+            if secs <= le {
+                *bucket += 1;
+            }
+        }
+        self.sum += secs;
+        self.count += 1;
+    }
+
+    fn render(&self, out: &mut String, name: &str, label: &str, value: &str) {
+        let value = escape(value);
+        for (bucket, le) in self.buckets.iter().zip(BUCKETS) {
+            let _ = writeln!(out, "{name}_bucket{{{label}=\"{value}\",le=\"{le}\"}} {bucket}");
+        }
+        let _ = writeln!(out, "{name}_bucket{{{label}=\"{value}\",le=\"+Inf\"}} {}", self.count);
+        let _ = writeln!(out, "{name}_sum{{{label}=\"{value}\"}} {}", self.sum);
+        let _ = writeln!(out, "{name}_count{{{label}=\"{value}\"}} {}", self.count);
+    }
+}
+
+impl Metrics {
+    fn observe_request(&self, method: &str, route: &str, status: StatusCode, took: Duration) {
+        let mut registry = self.0.lock().unwrap();
+        let key = (method.to_string(), route.to_string(), status.as_u16());
+        *registry.requests.entry(key).or_default() += 1;
+        registry.request_durations.entry(route.to_string()).or_default().observe(took);
+    }
+
+    fn observe_repo(&self, operation: &'static str, took: Duration) {
+        self.0.lock().unwrap().repo_durations.entry(operation).or_default().observe(took);
+    }
+
+    /// Renders all metrics with the number of tasks per section of every
+    /// list.
+    pub fn render(&self, tasks: &IndexMap<String, IndexMap<Section, usize>>) -> String {
+        let registry = self.0.lock().unwrap();
+        let mut out = String::new();
+
+        out.push_str("# HELP friday_http_requests_total Requests by method, route and status.\n");
+        out.push_str("# TYPE friday_http_requests_total counter\n");
+        for ((method, route, status), count) in &registry.requests {
+            let route = escape(route);
+            let _ = writeln!(
+                out,
+                "friday_http_requests_total{{method=\"{method}\",route=\"{route}\",\
+                 status=\"{status}\"}} {count}"
+            );
+        }
+
+        let name = "friday_http_request_duration_seconds";
+        let _ = writeln!(out, "# HELP {name} Duration of requests by route.");
+        let _ = writeln!(out, "# TYPE {name} histogram");
+        for (route, histogram) in &registry.request_durations {
+            histogram.render(&mut out, name, "route", route);
+        }
+
+        let name = "friday_repo_operation_duration_seconds";
+        let _ = writeln!(out, "# HELP {name} Duration of reading and writing the task files.");
+        let _ = writeln!(out, "# TYPE {name} histogram");
+        for (operation, histogram) in &registry.repo_durations {
+            histogram.render(&mut out, name, "operation", operation);
+        }
+
+        out.push_str("# HELP friday_tasks Tasks by list and section.\n");
+        out.push_str("# TYPE friday_tasks gauge\n");
+        for (list, sections) in tasks {
+            for (section, count) in sections {
+                let (list, section) = (escape(list), escape(&section.to_string()));
+                let _ =
+                    writeln!(out, "friday_tasks{{list=\"{list}\",section=\"{section}\"}} {count}");
+            }
+        }
+        out
+    }
+}
+
+// escape escapes label values as required by the text format
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Records the count and duration of requests by their route. The route is
+/// the path pattern, e.g. `/tasks/:section`, so ids don't end up in labels.
+pub async fn track<B>(State(metrics): State<Metrics>, req: Request<B>, next: Next<B>) -> Response {
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or_else(|| "unmatched".to_string(), |path| path.as_str().replace("%3A", ":"));
+    let method = req.method().to_string();
+    let start = Instant::now();
+    let res = next.run(req).await;
+    metrics.observe_request(&method, &route, res.status(), start.elapsed());
+    res
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// Timed is a [`Repo`] that records the duration of every operation of the
+/// wrapped one.
+pub struct Timed<R: Repo> {
+    repo: R,
+    metrics: Metrics,
+}
+
+impl<R: Repo> Timed<R> {
+    pub fn new(repo: R, metrics: Metrics) -> Timed<R> {
+        Timed { repo, metrics }
+    }
+
+    fn time<T>(&self, operation: &'static str, f: impl FnOnce(&R) -> T) -> T {
+        let start = Instant::now();
+        let res = f(&self.repo);
+        self.metrics.observe_repo(operation, start.elapsed());
+        res
+    }
+}
+
+impl<R: Repo> Repo for Timed<R> {
+    fn create(&self, task: &str, section: Section) -> Result<()> {
+        self.time("create", |r| r.create(task, section))
+    }
+
+    fn list(&self, section: Section) -> Result<Vec<String>> {
+        self.time("list", |r| r.list(section))
+    }
+
+    fn list_all(&self) -> Result<IndexMap<Section, Vec<String>>> {
+        self.time("list_all", Repo::list_all)
+    }
+
+    fn delete(&self, task: &str, section: Section) -> Result<()> {
+        self.time("delete", |r| r.delete(task, section))
+    }
+
+    fn update(&self, task: &str, new: &str, section: Section) -> Result<()> {
+        self.time("update", |r| r.update(task, new, section))
+    }
+
+    fn archive(&self, name: &str) -> Result<()> {
+        self.time("archive", |r| r.archive(name))
+    }
+
+    fn begin(&self) -> Result<()> {
+        self.time("begin", Repo::begin)
+    }
+
+    fn commit(&self) -> Result<()> {
+        self.time("commit", Repo::commit)
+    }
+
+    fn rollback(&self) {
+        self.time("rollback", Repo::rollback);
+    }
+
+    fn check(&self) -> Result<()> {
+        self.time("check", Repo::check)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_metrics() {
+        let metrics = Metrics::default();
+        metrics.observe_request("GET", "/tasks", StatusCode::OK, Duration::from_millis(3));
+        metrics.observe_request("GET", "/tasks", StatusCode::OK, Duration::from_millis(30));
+        metrics.observe_repo("list_all", Duration::from_millis(2));
+        let tasks = IndexMap::from([(
+            "default".to_string(),
+            IndexMap::from([(Section::Custom("to \"do\"".to_string()), 2)]),
+        )]);
+
+        let out = metrics.render(&tasks);
+        for line in [
+            "friday_http_requests_total{method=\"GET\",route=\"/tasks\",status=\"200\"} 2",
+            "friday_http_request_duration_seconds_bucket{route=\"/tasks\",le=\"0.001\"} 0",
+            "friday_http_request_duration_seconds_bucket{route=\"/tasks\",le=\"0.005\"} 1",
+            "friday_http_request_duration_seconds_bucket{route=\"/tasks\",le=\"+Inf\"} 2",
+            "friday_http_request_duration_seconds_count{route=\"/tasks\"} 2",
+            "friday_repo_operation_duration_seconds_count{operation=\"list_all\"} 1",
+            "friday_tasks{list=\"default\",section=\"to \\\"do\\\"\"} 2",
+        ] {
+            assert!(out.lines().any(|l| l == line), "expected {line} in\n{out}");
+        }
+    }
+}
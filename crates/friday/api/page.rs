@@ -0,0 +1,196 @@
+use std::ops::Range;
+
+use axum::http::{HeaderMap, HeaderValue};
+use friday_client::{ListResponse, NEXT_CURSOR_HEADER, TOTAL_COUNT_HEADER};
+use friday_core::{Section, Task};
+use indexmap::IndexMap;
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{Schema, SchemaObject},
+    JsonSchema,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::problem::{ErrorResponse, Kind};
+
+const DEFAULT_LIMIT: usize = 100;
+const MAX_LIMIT: usize = 1000;
+
+/// Query parameters to page through tasks and select their fields.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct PageParams {
+    /// the `next_cursor` of the previous page, without it the first page is
+    /// returned
+    cursor: Option<String>,
+    /// the maximum number of tasks per page, defaults to 100 and is capped at
+    /// 1000
+    limit: Option<usize>,
+    /// only include the given fields of the tasks, e.g. `id,text,due`
+    fields: Option<String>,
+}
+
+/// A task with only the fields selected with `fields`, all of them by
+/// default.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(transparent)]
+pub struct SelectedTask(Map<String, Value>);
+
+impl From<&Task> for SelectedTask {
+    fn from(task: &Task) -> Self {
+        match serde_json::to_value(task) {
+            Ok(Value::Object(task)) => SelectedTask(task),
+            _ => unreachable!("tasks are serialized as objects"),
+        }
+    }
+}
+
+// the schema is the one of tasks without required fields, so that both can't
+// differ
+impl JsonSchema for SelectedTask {
+    fn schema_name() -> String {
+        "SelectedTask".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let mut schema: SchemaObject = Task::json_schema(gen).into_object();
+        schema.object().required.clear();
+        schema.metadata().description =
+            Some("A task with only the selected fields, all of them by default.".to_string());
+        Schema::Object(schema)
+    }
+}
+
+type Result<T> = std::result::Result<T, ErrorResponse>;
+
+impl PageParams {
+    // range returns the indices of the tasks on the page.
+    fn range(&self, tasks: &[&Task]) -> Result<Range<usize>> {
+        let start = match &self.cursor {
+            Some(cursor) => Self::resume(tasks, cursor)?,
+            None => 0,
+        };
+        let limit = self.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+        if limit == 0 {
+            return Err(Kind::InvalidInput.response("limit needs to be at least 1"));
+        }
+        Ok(start..tasks.len().min(start + limit))
+    }
+
+    // cursor returns the cursor of the page starting at the index. It's the
+    // index and the id of the task before the page, like `2.e40c292c`.
+    fn cursor(tasks: &[&Task], start: usize) -> String {
+        format!("{start}.{}", tasks[start - 1].id)
+    }
+
+    // resume returns the index of the first task of the page of the cursor.
+    // If tasks were added or removed since the cursor was returned, the page
+    // starts after the task of the cursor anyway, so that none are skipped or
+    // returned twice. That isn't possible anymore once the task is gone.
+    fn resume(tasks: &[&Task], cursor: &str) -> Result<usize> {
+        let invalid = || Kind::InvalidInput.response(format!("invalid cursor {cursor}"));
+        let (start, id) = cursor.split_once('.').ok_or_else(invalid)?;
+        let start: usize = start.parse().map_err(|_| invalid())?;
+        if (1..=tasks.len()).contains(&start) && tasks[start - 1].id == id {
+            return Ok(start);
+        }
+        let moved = tasks.iter().position(|task| task.id == id).ok_or_else(|| {
+            Kind::InvalidInput.response(format!("cursor {cursor} is outdated, its task is gone"))
+        })?;
+        Ok(moved + 1)
+    }
+
+    // select serializes the task with only the requested fields.
+    fn select(task: &Task, fields: &[String]) -> Result<SelectedTask> {
+        let SelectedTask(mut task) = task.into();
+        if fields.is_empty() {
+            return Ok(SelectedTask(task));
+        }
+        if let Some(unknown) = fields.iter().find(|f| !task.contains_key(f.as_str())) {
+            return Err(Kind::InvalidInput.response(format!("unknown field {unknown}")));
+        }
+        task.retain(|key, _| fields.contains(key));
+        Ok(SelectedTask(task))
+    }
+
+    pub fn list(&self, tasks: &[Task]) -> Result<ListResponse<SelectedTask>> {
+        let tasks: Vec<&Task> = tasks.iter().collect();
+        let range = self.range(&tasks)?;
+        let fields = crate::split_param(self.fields.clone());
+        Ok(ListResponse {
+            items: tasks[range.clone()]
+                .iter()
+                .map(|t| Self::select(t, &fields))
+                .collect::<Result<_>>()?,
+            total: tasks.len(),
+            next_cursor: (range.end < tasks.len()).then(|| Self::cursor(&tasks, range.end)),
+        })
+    }
+
+    // sections pages through the tasks of all sections in order. Since the
+    // sections are returned as an object, the total and the next cursor are
+    // returned as headers. Empty sections are only part of the first page.
+    pub fn sections(
+        &self,
+        sections: &IndexMap<Section, Vec<Task>>,
+    ) -> Result<(HeaderMap, IndexMap<Section, Vec<SelectedTask>>)> {
+        let tasks: Vec<&Task> = sections.values().flatten().collect();
+        let total = tasks.len();
+        let range = self.range(&tasks)?;
+        let fields = crate::split_param(self.fields.clone());
+
+        let mut page: IndexMap<Section, Vec<SelectedTask>> = IndexMap::new();
+        let mut index = 0;
+        for (section, tasks) in sections {
+            if tasks.is_empty() && range.start == 0 {
+                page.insert(section.clone(), vec![]);
+            }
+            for task in tasks {
+                if range.contains(&index) {
+                    page.entry(section.clone()).or_default().push(Self::select(task, &fields)?);
+                }
+                index += 1;
+            }
+        }
+
+        let mut headers = HeaderMap::new();
+        headers.insert(TOTAL_COUNT_HEADER, HeaderValue::from(total));
+        if range.end < total {
+            let cursor = Self::cursor(&tasks, range.end);
+            // ids are hex digits, so the cursor is always a valid header value
+            headers.insert(NEXT_CURSOR_HEADER, HeaderValue::from_str(&cursor).unwrap());
+        }
+        Ok((headers, page))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tasks(texts: &[&str]) -> Vec<Task> {
+        texts.iter().map(|t| t.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn resume_after_the_task_of_the_cursor() {
+        let tasks = tasks(&["first", "second", "third"]);
+        let all: Vec<&Task> = tasks.iter().collect();
+        let cursor = PageParams::cursor(&all, 2);
+        assert_eq!(PageParams::resume(&all, &cursor).ok(), Some(2));
+
+        let without_first: Vec<&Task> = tasks[1..].iter().collect();
+        assert_eq!(PageParams::resume(&without_first, &cursor).ok(), Some(1));
+        let without_second: Vec<&Task> = vec![&tasks[0], &tasks[2]];
+        assert!(PageParams::resume(&without_second, &cursor).is_err());
+    }
+
+    #[test]
+    fn resume_fails_for_invalid_cursors() {
+        let tasks = tasks(&["first"]);
+        let all: Vec<&Task> = tasks.iter().collect();
+        for cursor in ["", "1", "x.abc", "-1.abc"] {
+            assert!(PageParams::resume(&all, cursor).is_err(), "{cursor}");
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use axum::{
+    http::{header, HeaderName, StatusCode},
+    Json,
+};
+use friday_client::{Candidate, Problem};
+
+/// the media type of error responses, see RFC 7807
+const PROBLEM_JSON: &str = "application/problem+json";
+
+// the problem is boxed to keep the `Result`s of the handlers small
+pub type ErrorResponse = (StatusCode, [(HeaderName, &'static str); 1], Json<Box<Problem>>);
+
+/// Kind is the type of a [`Problem`], each kind has a fixed status and title.
+#[derive(Clone, Copy)]
+pub enum Kind {
+    ListNotFound,
+    SectionNotFound,
+    TaskNotFound,
+    AmbiguousMatch,
+    InvalidInput,
+    Unauthorized,
+    Forbidden,
+    TooLarge,
+    TooManyRequests,
+    Timeout,
+    Unavailable,
+    Internal,
+}
+
+impl Kind {
+    fn status(self) -> StatusCode {
+        match self {
+            Kind::ListNotFound | Kind::SectionNotFound | Kind::TaskNotFound => {
+                StatusCode::NOT_FOUND
+            }
+            Kind::AmbiguousMatch => StatusCode::CONFLICT,
+            Kind::InvalidInput => StatusCode::BAD_REQUEST,
+            Kind::Unauthorized => StatusCode::UNAUTHORIZED,
+            Kind::Forbidden => StatusCode::FORBIDDEN,
+            Kind::TooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            Kind::TooManyRequests => StatusCode::TOO_MANY_REQUESTS,
+            Kind::Timeout => StatusCode::REQUEST_TIMEOUT,
+            Kind::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+            Kind::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn slug(self) -> &'static str {
+        match self {
+            Kind::ListNotFound => "list-not-found",
+            Kind::SectionNotFound => "section-not-found",
+            Kind::TaskNotFound => "task-not-found",
+            Kind::AmbiguousMatch => "ambiguous-match",
+            Kind::InvalidInput => "invalid-input",
+            Kind::Unauthorized => "unauthorized",
+            Kind::Forbidden => "forbidden",
+            Kind::TooLarge => "too-large",
+            Kind::TooManyRequests => "too-many-requests",
+            Kind::Timeout => "timeout",
+            Kind::Unavailable => "unavailable",
+            Kind::Internal => "internal",
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Kind::ListNotFound => "The list doesn't exist",
+            Kind::SectionNotFound => "The section doesn't exist",
+            Kind::TaskNotFound => "No task matches",
+            Kind::AmbiguousMatch => "More than one task matches",
+            Kind::InvalidInput => "The input is invalid",
+            Kind::Unauthorized => "Authentication is required",
+            Kind::Forbidden => "The token lacks the required scope",
+            Kind::TooLarge => "The request or task is too large",
+            Kind::TooManyRequests => "Too many requests",
+            Kind::Timeout => "The request took too long",
+            Kind::Unavailable => "The service is unavailable",
+            Kind::Internal => "Internal error",
+        }
+    }
+
+    pub fn problem(self, detail: impl Into<String>) -> Problem {
+        Problem {
+            kind: format!("urn:friday:problem:{}", self.slug()),
+            title: self.title().to_string(),
+            status: self.status().as_u16(),
+            detail: detail.into(),
+            candidates: vec![],
+            pattern: None,
+            section: None,
+            max_length: None,
+            operation: None,
+        }
+    }
+
+    pub fn response(self, detail: impl Into<String>) -> ErrorResponse {
+        response(self.problem(detail))
+    }
+}
+
+fn response(problem: Problem) -> ErrorResponse {
+    let status = StatusCode::from_u16(problem.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    (status, [(header::CONTENT_TYPE, PROBLEM_JSON)], Json(Box::new(problem)))
+}
+
+pub fn to_http_err(e: friday_core::Error) -> ErrorResponse {
+    let mut problem = kind(&e).problem(e.to_string());
+    describe(&mut problem, e);
+    response(problem)
+}
+
+// failed batches are reported like their failed operation
+fn kind(e: &friday_core::Error) -> Kind {
+    match e {
+        friday_core::Error::SectionNotFound(_) => Kind::SectionNotFound,
+        friday_core::Error::TaskNotFound(_) => Kind::TaskNotFound,
+        friday_core::Error::AmbiguousMatch { .. } => Kind::AmbiguousMatch,
+        friday_core::Error::EmptyTask
+        | friday_core::Error::InvalidArgument(_)
+        | friday_core::Error::InvalidCommand(_) => Kind::InvalidInput,
+        friday_core::Error::TaskTooLong(_) => Kind::TooLarge,
+        friday_core::Error::BatchFailed { source, .. } => kind(source),
+        friday_core::Error::IO(_) => Kind::Internal,
+    }
+}
+
+// describe adds the members clients need to tell what failed, failed batches
+// are described by their failed operation
+fn describe(problem: &mut Problem, e: friday_core::Error) {
+    match e {
+        friday_core::Error::SectionNotFound(section) => {
+            problem.section = Some(section.to_string());
+        }
+        friday_core::Error::TaskNotFound(pattern) => problem.pattern = Some(pattern),
+        friday_core::Error::AmbiguousMatch { pattern, candidates } => {
+            problem.pattern = Some(pattern);
+            problem.candidates = candidates
+                .into_iter()
+                .map(|c| Candidate { section: c.section.to_string(), id: c.id, task: c.task })
+                .collect();
+        }
+        friday_core::Error::TaskTooLong(max) => problem.max_length = Some(max),
+        friday_core::Error::BatchFailed { index, source } => {
+            problem.operation = Some(index);
+            describe(problem, *source);
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,86 @@
+use std::{env, net::SocketAddr, path::PathBuf};
+
+use axum::{
+    http::{header, HeaderMap, Uri},
+    response::Redirect,
+    Router,
+};
+use axum_server::tls_rustls::RustlsConfig;
+
+use crate::problem::{ErrorResponse, Kind};
+
+/// Tls is the certificate and key `fridaypi` serves HTTPS with. Both are read
+/// from PEM files.
+pub struct Tls {
+    cert: PathBuf,
+    key: PathBuf,
+}
+
+impl Tls {
+    /// Reads the paths of the certificate and the key from `FRIDAY_TLS_CERT`
+    /// and `FRIDAY_TLS_KEY`. Without them plain HTTP is served.
+    pub fn from_env() -> Option<Tls> {
+        match (env::var("FRIDAY_TLS_CERT"), env::var("FRIDAY_TLS_KEY")) {
+            (Ok(cert), Ok(key)) => Some(Tls { cert: cert.into(), key: key.into() }),
+            (Err(_), Err(_)) => None,
+            _ => panic!("FRIDAY_TLS_CERT and FRIDAY_TLS_KEY should be set together"),
+        }
+    }
+
+    pub async fn config(&self) -> RustlsConfig {
+        RustlsConfig::from_pem_file(&self.cert, &self.key).await.unwrap_or_else(|e| {
+            panic!("failed to load {} and {}: {e}", self.cert.display(), self.key.display())
+        })
+    }
+}
+
+/// Serves plain HTTP on the address and redirects all requests to the same
+/// path on the HTTPS port.
+pub async fn redirect(addr: SocketAddr, https_port: u16) {
+    let app = Router::new().fallback(move |headers: HeaderMap, uri: Uri| async move {
+        redirect_uri(&headers, &uri, https_port).map(|uri| Redirect::permanent(&uri))
+    });
+    tracing::info!("redirecting {addr} to https");
+    axum::Server::bind(&addr).serve(app.into_make_service()).await.unwrap();
+}
+
+// redirect_uri builds the HTTPS uri from the host of the request, the port of
+// the host is replaced with the one HTTPS is served on.
+fn redirect_uri(headers: &HeaderMap, uri: &Uri, https_port: u16) -> Result<String, ErrorResponse> {
+    let host = headers
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok())
+        .and_then(|host| host.parse::<Uri>().ok())
+        .and_then(|host| host.host().map(String::from))
+        .ok_or_else(|| Kind::InvalidInput.response("the Host header is missing or invalid"))?;
+    let path = uri.path_and_query().map_or("/", |p| p.as_str());
+    if https_port == 443 {
+        return Ok(format!("https://{host}{path}"));
+    }
+    Ok(format!("https://{host}:{https_port}{path}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    fn headers(host: &str) -> HeaderMap {
+        HeaderMap::from_iter([(header::HOST, HeaderValue::from_str(host).unwrap())])
+    }
+
+    #[test]
+    fn redirect_uri_replaces_scheme_and_port() {
+        let uri: Uri = "/tasks?tag=infra".parse().unwrap();
+        let redirect = redirect_uri(&headers("friday.home:8080"), &uri, 8443).unwrap();
+        assert_eq!(redirect, "https://friday.home:8443/tasks?tag=infra");
+        let redirect = redirect_uri(&headers("friday.home"), &uri, 443).unwrap();
+        assert_eq!(redirect, "https://friday.home/tasks?tag=infra");
+    }
+
+    #[test]
+    fn redirect_uri_requires_host() {
+        assert!(redirect_uri(&HeaderMap::new(), &Uri::from_static("/"), 443).is_err());
+    }
+}
@@ -0,0 +1,118 @@
+use std::io::BufRead;
+
+use clap::Parser;
+use friday_core::Op;
+
+use crate::error::{Error, Result};
+
+/// A line of a batch, it's parsed like the arguments of the command with the
+/// same name. Words are split at whitespace, so quotes aren't needed.
+#[derive(Parser, Debug)]
+#[command(no_binary_name = true)]
+enum Line {
+    Add {
+        #[arg(required = true)]
+        task: Vec<String>,
+        #[arg(short, long)]
+        section: Option<String>,
+    },
+    Rm {
+        #[arg(required = true)]
+        pattern: Vec<String>,
+        #[arg(short, long)]
+        section: Option<String>,
+    },
+    /// moves the task from `--section` to `--to`
+    Mv {
+        #[arg(required = true)]
+        pattern: Vec<String>,
+        #[arg(short, long)]
+        section: Option<String>,
+        #[arg(short, long)]
+        to: String,
+    },
+    Done {
+        #[arg(required = true)]
+        pattern: Vec<String>,
+        #[arg(short, long)]
+        section: Option<String>,
+    },
+}
+
+/// Parses one command per line, empty lines and lines starting with `#` are
+/// skipped. Sections default to the given one like for the single commands.
+pub fn parse(input: impl BufRead, default_section: &str) -> Result<Vec<Op>> {
+    let mut ops = vec![];
+    for (i, line) in input.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let parsed = Line::try_parse_from(line.split_whitespace()).map_err(|e| {
+            // clap errors also contain the usage, only the reason is relevant
+            let reason = e.to_string();
+            let reason = reason.lines().next().unwrap_or_default().trim_start_matches("error: ");
+            Error::InvalidArgument(format!("line {}: {reason}", i + 1))
+        })?;
+        let section = |section: Option<String>| section.or_else(|| Some(default_section.into()));
+        ops.push(match parsed {
+            Line::Add { task, section: s } => Op::Add { task: task.join(" "), section: section(s) },
+            Line::Rm { pattern, section: s } => {
+                Op::Rm { pattern: pattern.join(" "), section: section(s) }
+            }
+            Line::Mv { pattern, section: s, to } => {
+                Op::Mv { pattern: pattern.join(" "), from: section(s), to: Some(to) }
+            }
+            Line::Done { pattern, section: s } => {
+                Op::Complete { pattern: pattern.join(" "), section: section(s) }
+            }
+        });
+    }
+    Ok(ops)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! parse_tests {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (input, expected): (&str, Option<Vec<Op>>) = $value;
+                    assert_eq!(parse(input.as_bytes(), "dump").ok(), expected);
+                }
+            )*
+        }
+    }
+
+    fn s(value: &str) -> String {
+        value.to_string()
+    }
+
+    parse_tests! {
+        parse_add: (
+            "add -s todo update deps #infra",
+            Some(vec![Op::Add { task: s("update deps #infra"), section: Some(s("todo")) }]),
+        ),
+        parse_defaults_section: (
+            "rm deps",
+            Some(vec![Op::Rm { pattern: s("deps"), section: Some(s("dump")) }]),
+        ),
+        parse_mv: (
+            "mv deps --to todo",
+            Some(vec![Op::Mv { pattern: s("deps"), from: Some(s("dump")), to: Some(s("todo")) }]),
+        ),
+        parse_skips_empty_lines_and_comments: (
+            "\n# comment\n  done  review PR \n",
+            Some(vec![Op::Complete { pattern: s("review PR"), section: Some(s("dump")) }]),
+        ),
+        parse_nothing: ("", Some(vec![])),
+        parse_errors_on_unknown_command: ("show", None),
+        parse_errors_on_missing_args: ("add -s todo", None),
+        parse_errors_on_missing_target: ("mv deps", None),
+    }
+}
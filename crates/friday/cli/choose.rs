@@ -0,0 +1,78 @@
+use std::io::{BufRead, Write};
+
+use friday_core::Candidate;
+
+use crate::error::{Error, Result};
+
+/// Writes the candidates numbered starting from 1.
+pub fn list(candidates: &[Candidate], mut output: impl Write) -> Result<()> {
+    for (i, c) in candidates.iter().enumerate() {
+        writeln!(output, "{:>3}) {} ({}, id {})", i + 1, c.task, c.section, c.id)?;
+    }
+    Ok(())
+}
+
+/// Lets the user choose one of the candidates by entering its number.
+pub fn choose(
+    candidates: &[Candidate],
+    input: impl BufRead,
+    mut output: impl Write,
+) -> Result<&Candidate> {
+    list(candidates, &mut output)?;
+    write!(output, "choose a task [1-{}]: ", candidates.len())?;
+    output.flush()?;
+
+    let line = input.lines().next().transpose()?.unwrap_or_default();
+    let choice = line.trim();
+    choice
+        .parse::<usize>()
+        .ok()
+        .and_then(|n| n.checked_sub(1))
+        .and_then(|i| candidates.get(i))
+        .ok_or_else(|| Error::InvalidArgument(format!("invalid choice '{choice}'")))
+}
+
+#[cfg(test)]
+mod tests {
+    use friday_core::Section;
+
+    use super::*;
+
+    fn candidates() -> Vec<Candidate> {
+        ["update deps", "review deps PR"]
+            .iter()
+            .map(|t| Candidate {
+                section: Section::Dump,
+                id: t.len().to_string(),
+                task: t.to_string(),
+            })
+            .collect()
+    }
+
+    macro_rules! choose_tests {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (input, expected) = $value;
+                    let candidates = candidates();
+                    let mut output = vec![];
+                    let chosen = choose(&candidates, input.as_bytes(), &mut output);
+                    assert_eq!(chosen.ok().map(|c| c.task.as_str()), expected);
+
+                    let output = String::from_utf8(output).unwrap();
+                    assert!(output.starts_with("  1) update deps (dump, id 11)\n  2) review deps PR"));
+                }
+            )*
+        }
+    }
+
+    choose_tests! {
+        choose_first: ("1\n", Some("update deps")),
+        choose_second: (" 2 \n", Some("review deps PR")),
+        choose_zero: ("0\n", None),
+        choose_out_of_range: ("3\n", None),
+        choose_nothing: ("", None),
+        choose_text: ("deps\n", None),
+    }
+}
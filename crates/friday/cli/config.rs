@@ -1,89 +1,419 @@
-use std::{collections::HashMap, convert, fmt, str};
+use std::{
+    collections::HashMap,
+    fs,
+    io,
+    path::{Path, PathBuf},
+};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+use friday_core::{DueFilter, Query, Sort};
+use indexmap::IndexMap;
+use owo_colors::{AnsiColors, DynColors};
+use serde::{Deserialize, Serialize};
 
 use crate::Error;
 
 const DEFAULT_FILE: &str = "friday.md";
+const DEFAULT_SECTION: &str = "dump";
+const DEFAULT_EDITOR: &str = "vi";
+const CONFIG_FILE: &str = "friday/config.toml";
+/// the name of the list stored in the file set by `--file`, `FRIDAY_FILE` or
+/// `file` in the config
+pub const DEFAULT_LIST: &str = "default";
 
-#[derive(Debug, PartialEq, Eq)]
+/// This binary lets you manage stuff to do on fridays.
+///
+/// Tasks can be annotated with `due:<date>`, e.g. `due:2026-10-23` or
+/// `due:next-friday`. Priorities are set with `!1` to `!3` or `(A)` to `(C)`.
+/// Tasks annotated with `every:week` or e.g. `every:friday` recur, completing
+/// one with `done` adds the next instance.
+///
+/// Settings are read from `$XDG_CONFIG_HOME/friday/config.toml` or the file
+/// in `FRIDAY_CONFIG`. Flags take precedence over env vars, which take
+/// precedence over the config file. Run `friday config` to see the result.
+///
+/// With `FRIDAY_REMOTE` or `remote` in the config set to the url of a
+/// `fridaypi` server, the lists of the server are used instead of local files.
+/// If the server requires authentication, the token is read from
+/// `FRIDAY_TOKEN`.
+///
+/// Added tasks are attributed to `$USER` and stored with the time they were
+/// added. Tasks annotated with `assignee:<name>` are shown by `show --mine`
+/// if the name matches `$USER`.
+#[derive(Parser, Debug, PartialEq, Eq)]
+#[command(version)]
+pub struct Cli {
+    /// The file that should be used, can also be configured using the
+    /// `FRIDAY_FILE` env var or `file` in the config [default: ~/friday.md]
+    #[arg(short, long, global = true)]
+    pub file: Option<String>,
+    /// The list that should be used, lists are configured in the `lists`
+    /// table of the config and can also be selected using the `FRIDAY_LIST`
+    /// env var [default: default]
+    #[arg(short, long, global = true, conflicts_with = "file")]
+    pub list: Option<String>,
+    #[command(subcommand)]
+    pub action: Option<Action>,
+}
+
+#[derive(Subcommand, Debug, PartialEq, Eq)]
 pub enum Action {
-    Show,
-    Add,
-    Help,
+    /// Add a task to the end of a section.
+    Add {
+        #[arg(required = true)]
+        task: Vec<String>,
+        /// The section to add the task to, can also be configured using the
+        /// `FRIDAY_SECTION` env var or `section` in the config [default: dump]
+        #[arg(short, long)]
+        section: Option<String>,
+    },
+    /// Show the contents of the file.
+    Show(ShowArgs),
+    /// Check off the task matching the pattern.
+    Done {
+        #[arg(required = true)]
+        pattern: Vec<String>,
+        /// The section to search the task in, defaults like for `add`.
+        #[arg(short, long)]
+        section: Option<String>,
+    },
+    /// Remove the task matching the pattern.
+    Rm {
+        #[arg(required = true)]
+        pattern: Vec<String>,
+        /// The section to search the task in, defaults like for `add`.
+        #[arg(short, long)]
+        section: Option<String>,
+    },
+    /// Run newline-delimited add, rm, mv and done commands from stdin, e.g.
+    /// `friday batch < ops.txt`. If one of them fails, none is applied.
+    Batch,
+    /// Search all sections for tasks fuzzily matching the query, best
+    /// matches first.
+    Find {
+        #[arg(required = true)]
+        query: Vec<String>,
+    },
+    /// Add the next instance of completed recurring tasks.
+    Rollover,
+    /// Archive the current week next to the file and start a new one with
+    /// all unfinished tasks.
+    NewWeek,
+    /// Open the file in $EDITOR.
     Edit,
+    /// Browse and edit the tasks interactively.
+    Tui,
+    /// Print the version.
     Version,
+    /// Print the resolved settings in the format of the config file.
+    Config,
+    /// Print all lists with their files, the one in use is marked with `*`.
+    Lists,
+    /// Print shell completions, e.g. `friday completions zsh > _friday`.
+    Completions { shell: Shell },
+}
+
+#[derive(Args, Debug, Default, PartialEq, Eq)]
+pub struct ShowArgs {
+    /// Only show tasks tagged with #<TAG>.
+    #[arg(long = "tag", value_name = "TAG")]
+    pub tags: Vec<String>,
+    /// Only show tasks mentioning @<PERSON>.
+    #[arg(long = "person", value_name = "PERSON")]
+    pub people: Vec<String>,
+    /// Only show tasks assigned to $USER with `assignee:<name>`.
+    #[arg(long)]
+    pub mine: bool,
+    /// Only show tasks due until the filter, one of overdue, today,
+    /// this-week, next-week or a date.
+    #[arg(long)]
+    pub due: Option<DueFilter>,
+    /// Sort tasks within each section by due date or priority, the file
+    /// itself stays unchanged.
+    #[arg(long)]
+    pub sort: Option<Sort>,
+    /// The output format, can also be configured using the `FRIDAY_FORMAT`
+    /// env var or `format` in the config [default: markdown]
+    #[arg(long, value_enum)]
+    pub format: Option<Format>,
+}
+
+impl From<ShowArgs> for Query {
+    fn from(args: ShowArgs) -> Self {
+        Query {
+            tags: args.tags,
+            people: args.people,
+            due: args.due,
+            assignee: None,
+            sort: args.sort,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    /// The same JSON as returned by `GET /tasks` of the API.
+    Json,
+    /// The same structure as `json` but as YAML.
+    Yaml,
+    /// Colored markdown.
+    #[default]
+    Markdown,
+    /// One task per line prefixed with the section, separated by a tab.
+    Plain,
+}
+
+/// Colors used for the output, either names like `cyan` and `bright red` or
+/// hex codes like `#ff8800`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme {
+    /// section headers
+    pub section: Color,
+    /// overdue tasks
+    pub overdue: Color,
+    /// matched characters in `find`
+    pub highlight: Color,
 }
 
-impl convert::TryFrom<&str> for Action {
-    type Error = Error;
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        match value {
-            "add" => Ok(Action::Add),
-            "show" => Ok(Action::Show),
-            "edit" => Ok(Action::Edit),
-            "version" => Ok(Action::Version),
-            "help" | "" => Ok(Action::Help),
-            cmd => Err(Error::InvalidCommand(cmd.to_string())),
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            section: Color::ansi("cyan", AnsiColors::Cyan),
+            overdue: Color::ansi("red", AnsiColors::Red),
+            highlight: Color::ansi("yellow", AnsiColors::Yellow),
         }
     }
 }
 
-impl str::FromStr for Action {
-    type Err = Error;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Action::try_from(s)
+/// A color of the [`Theme`] that keeps the name it was configured with.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+pub struct Color {
+    name: String,
+    pub color: DynColors,
+}
+
+impl Color {
+    fn ansi(name: &str, color: AnsiColors) -> Self {
+        Self { name: name.to_string(), color: DynColors::Ansi(color) }
+    }
+}
+
+impl TryFrom<String> for Color {
+    type Error = String;
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        let color = name.parse().map_err(|_| format!("invalid color {name}"))?;
+        Ok(Self { name, color })
+    }
+}
+
+impl From<Color> for String {
+    fn from(color: Color) -> Self {
+        color.name
     }
 }
 
-impl fmt::Display for Action {
-    // This uses the autogenerated debug trait from `#[derive(Debug)]`
-    // to display the enum name.
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{self:?}")
+/// Settings is the content of the config file. Everything is optional, unset
+/// values fall back to the defaults.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    pub file: Option<String>,
+    pub section: Option<String>,
+    pub editor: Option<String>,
+    pub format: Option<Format>,
+    /// the url of a `fridaypi` server to use instead of the files
+    pub remote: Option<String>,
+    pub theme: Theme,
+    /// additional lists by name with the file they're stored in
+    pub lists: IndexMap<String, String>,
+}
+
+impl Settings {
+    /// Reads the settings from the file, a missing file is the same as an
+    /// empty one.
+    pub fn load(path: &Path) -> Result<Settings, Error> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Settings::default()),
+            Err(e) => return Err(e.into()),
+        };
+        toml::from_str(&content).map_err(|e| {
+            Error::InvalidArgument(format!("invalid config file {}: {e}", path.display()))
+        })
     }
 }
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct Config {
-    pub action: Action,
-    pub input: Option<String>,
+    pub action: Option<Action>,
+    /// the file of the selected list
     pub file: String,
+    /// the name of the selected list
+    pub list: String,
+    /// all lists including the default one
+    pub lists: IndexMap<String, String>,
+    pub section: String,
+    pub editor: String,
+    pub format: Format,
+    pub remote: Option<String>,
+    /// the token sent to the remote, it's only read from the env to not
+    /// store it in the config file
+    pub token: Option<String>,
+    /// the author of added tasks, it's read from `$USER`
+    pub user: Option<String>,
+    pub theme: Theme,
 }
 
 impl Config {
     // TODO: add doc test here: https://doc.rust-lang.org/book/ch14-02-publishing-to-crates-io.html#documentation-comments-as-tests
-    pub fn build(
-        mut args: impl Iterator<Item = String>,
-        env_vars: &HashMap<String, String>,
-    ) -> Result<Config, Error> {
-        // first item is binary name
-        args.next();
-
-        let action: Action = args.next().unwrap_or_default().as_str().parse()?;
-
-        let input = args.reduce(|mut iter, arg| {
-            iter += &format!(" {arg}");
-            iter
-        });
-
-        let mut file = env_vars.get("FRIDAY_FILE").cloned().unwrap_or_default();
-        if file.trim().is_empty() {
-            let home = dirs::home_dir().ok_or_else(|| {
-                Error::InvalidArgument("failed to get users home dir".to_string())
-            })?;
+    /// Resolves the settings from the flags, the env vars and the config file,
+    /// in that order.
+    pub fn build(cli: Cli, env_vars: &HashMap<String, String>) -> Result<Config, Error> {
+        let settings = Settings::load(&config_path(env_vars)?)?;
+        // empty values are treated as unset, e.g. for `EDITOR=""`
+        let env = |key: &str| env_vars.get(key).filter(|v| !v.trim().is_empty()).cloned();
+
+        let default_file = match cli.file.or_else(|| env("FRIDAY_FILE")).or(settings.file) {
+            Some(file) if !file.trim().is_empty() => expand_home(&file)?,
             // since home dir is always a valid path and `DEFAULT_FILE` also
             // there won't be any loss when converting.
-            file = home.join(DEFAULT_FILE).to_string_lossy().to_string();
+            _ => home_dir()?.join(DEFAULT_FILE).to_string_lossy().to_string(),
+        };
+        if settings.lists.contains_key(DEFAULT_LIST) {
+            return Err(Error::InvalidArgument(format!(
+                "list name {DEFAULT_LIST} is reserved for the file, use `file` instead"
+            )));
+        }
+        let mut lists = IndexMap::from([(DEFAULT_LIST.to_string(), default_file)]);
+        for (name, file) in settings.lists {
+            lists.insert(name, expand_home(&file)?);
         }
+        let list =
+            cli.list.or_else(|| env("FRIDAY_LIST")).unwrap_or_else(|| DEFAULT_LIST.to_string());
+        let file = lists.get(&list).cloned().ok_or_else(|| {
+            let names: Vec<&str> = lists.keys().map(String::as_str).collect();
+            Error::InvalidArgument(format!(
+                "unknown list {list}, expected one of {}",
+                names.join(", ")
+            ))
+        })?;
+        let section = env("FRIDAY_SECTION")
+            .or(settings.section)
+            .unwrap_or_else(|| DEFAULT_SECTION.to_string());
+        let editor = env("EDITOR")
+            .or(settings.editor.filter(|e| !e.trim().is_empty()))
+            .unwrap_or_else(|| DEFAULT_EDITOR.to_string());
+        let format_env = env("FRIDAY_FORMAT")
+            .map(|f| Format::from_str(&f, true).map_err(Error::InvalidArgument))
+            .transpose()?;
+        let format_flag = match &cli.action {
+            Some(Action::Show(args)) => args.format,
+            _ => None,
+        };
+        let format = format_flag.or(format_env).or(settings.format).unwrap_or_default();
+        let remote = env("FRIDAY_REMOTE").or(settings.remote);
+        let token = env("FRIDAY_TOKEN");
+        let user = env("USER");
+
+        Ok(Config {
+            action: cli.action,
+            file,
+            list,
+            lists,
+            section,
+            editor,
+            format,
+            remote,
+            token,
+            user,
+            theme: settings.theme,
+        })
+    }
+}
+
+impl From<&Config> for Settings {
+    fn from(cfg: &Config) -> Self {
+        let mut lists = cfg.lists.clone();
+        let file = lists.shift_remove(DEFAULT_LIST);
+        Settings {
+            file,
+            section: Some(cfg.section.clone()),
+            editor: Some(cfg.editor.clone()),
+            format: Some(cfg.format),
+            remote: cfg.remote.clone(),
+            theme: cfg.theme.clone(),
+            lists,
+        }
+    }
+}
+
+fn config_path(env_vars: &HashMap<String, String>) -> Result<PathBuf, Error> {
+    if let Some(path) = env_vars.get("FRIDAY_CONFIG").filter(|p| !p.trim().is_empty()) {
+        return Ok(PathBuf::from(path));
+    }
+    let config_dir = dirs::config_dir()
+        .ok_or_else(|| Error::InvalidArgument("failed to get users config dir".to_string()))?;
+    Ok(config_dir.join(CONFIG_FILE))
+}
 
-        Ok(Config { action, input, file })
+// expand_home replaces a leading `~` with the users home dir since paths in
+// the config file are not expanded by the shell.
+fn expand_home(path: &str) -> Result<String, Error> {
+    match path.strip_prefix("~/") {
+        Some(rest) => Ok(home_dir()?.join(rest).to_string_lossy().to_string()),
+        None => Ok(path.to_string()),
     }
 }
 
+fn home_dir() -> Result<PathBuf, Error> {
+    dirs::home_dir()
+        .ok_or_else(|| Error::InvalidArgument("failed to get users home dir".to_string()))
+}
+
 #[cfg(test)]
 mod tests {
+    use clap::CommandFactory;
+    use tempfile::TempDir;
+
     use super::*;
     use crate::Result;
 
+    // build parses the args, unless set in the env vars a missing config file
+    // is used so that the one of the user doesn't influence the tests.
+    fn build(args: &[&str], env_vars: &HashMap<String, String>) -> Result<Config> {
+        let cli = Cli::try_parse_from([&["binary"], args].concat())
+            .map_err(|e| Error::InvalidArgument(e.to_string()))?;
+        let tmp_dir = TempDir::new()?;
+        let mut env_vars = env_vars.clone();
+        env_vars
+            .entry("FRIDAY_CONFIG".to_string())
+            .or_insert_with(|| tmp_dir.path().join("missing.toml").to_string_lossy().to_string());
+        Config::build(cli, &env_vars)
+    }
+
+    fn build_with_settings(
+        args: &[&str],
+        env_vars: &[(&str, &str)],
+        settings: &str,
+    ) -> Result<Config> {
+        let tmp_dir = TempDir::new()?;
+        let config_file = tmp_dir.path().join("config.toml");
+        fs::write(&config_file, settings)?;
+        let mut env_vars: HashMap<String, String> =
+            env_vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        env_vars.insert("FRIDAY_CONFIG".to_string(), config_file.to_string_lossy().to_string());
+        build(args, &env_vars)
+    }
+
+    #[test]
+    fn cli_is_valid() {
+        Cli::command().debug_assert();
+    }
+
     // This is a way to create table driven tests.
     // It's definitely overkill here but whatever.
     // https://users.rust-lang.org/t/table-driven-aka-data-driven-testing/3848
@@ -91,14 +421,11 @@ mod tests {
         ($name:ident, $($in:expr),+ => $out:expr) => {
             #[test]
             fn $name() -> Result<()> {
-                let args: Vec<String> = vec!["binary", "add", $($in, )*]
-                    .iter()
-                    .map(|&s| s.to_string())
-                    .collect();
-                let cfg = Config::build(args.into_iter(), &HashMap::new())?;
-                assert!(cfg.input.is_some());
-                let input = cfg.input.unwrap();
-                assert_eq!(input, $out.to_string());
+                let cfg = build(&["add", $($in, )*], &HashMap::new())?;
+                let Some(Action::Add { task, .. }) = cfg.action else {
+                    panic!("expected add action, got {:?}", cfg.action);
+                };
+                assert_eq!(task.join(" "), $out.to_string());
                 Ok(())
             }
         };
@@ -117,17 +444,185 @@ mod tests {
     fn config_is_created() -> Result<()> {
         let friday_file = "testing".to_string();
 
-        let args = vec!["binary".to_string(), "show".to_string()];
         let env_vars = HashMap::from([("FRIDAY_FILE".to_string(), friday_file.clone())]);
-        let cfg = Config::build(args.into_iter(), &env_vars)?;
-        assert_eq!(cfg, Config { action: Action::Show, file: friday_file, input: None });
+        let cfg = build(&["show"], &env_vars)?;
+        assert_eq!(
+            cfg,
+            Config {
+                action: Some(Action::Show(ShowArgs::default())),
+                file: friday_file.clone(),
+                list: DEFAULT_LIST.to_string(),
+                lists: IndexMap::from([(DEFAULT_LIST.to_string(), friday_file)]),
+                section: "dump".to_string(),
+                editor: "vi".to_string(),
+                format: Format::Markdown,
+                remote: None,
+                token: None,
+                user: None,
+                theme: Theme::default(),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn config_reads_settings_file() -> Result<()> {
+        let settings = r##"
+            file = "/tmp/friday.md"
+            section = "todo"
+            editor = "code -w"
+            format = "plain"
+
+            [theme]
+            section = "#ff8800"
+        "##;
+        let cfg = build_with_settings(&["show"], &[], settings)?;
+        assert_eq!(cfg.file, "/tmp/friday.md");
+        assert_eq!(cfg.section, "todo");
+        assert_eq!(cfg.editor, "code -w");
+        assert_eq!(cfg.format, Format::Plain);
+        assert_eq!(cfg.theme.section.color, DynColors::Rgb(0xff, 0x88, 0x00));
+        assert_eq!(cfg.theme.overdue, Theme::default().overdue);
+        Ok(())
+    }
+
+    #[test]
+    fn config_prefers_env_over_settings_file() -> Result<()> {
+        let settings = "file = \"from-config\"\nsection = \"todo\"\nformat = \"plain\"";
+        let env_vars = [("FRIDAY_FILE", "from-env"), ("FRIDAY_FORMAT", "yaml")];
+        let cfg = build_with_settings(&["show"], &env_vars, settings)?;
+        assert_eq!(cfg.file, "from-env");
+        assert_eq!(cfg.section, "todo");
+        assert_eq!(cfg.format, Format::Yaml);
+
+        let cfg = build_with_settings(&["show", "--format", "json"], &env_vars, settings)?;
+        assert_eq!(cfg.format, Format::Json);
+        Ok(())
+    }
+
+    #[test]
+    fn config_selects_list() -> Result<()> {
+        let settings =
+            "file = \"personal.md\"\n[lists]\nteam = \"team.md\"\noncall = \"oncall.md\"";
+        let cfg = build_with_settings(&["-l", "team", "show"], &[], settings)?;
+        assert_eq!(cfg.list, "team");
+        assert_eq!(cfg.file, "team.md");
+        assert_eq!(cfg.lists.keys().collect::<Vec<_>>(), vec!["default", "team", "oncall"]);
+
+        let cfg = build_with_settings(&["show"], &[("FRIDAY_LIST", "oncall")], settings)?;
+        assert_eq!(cfg.file, "oncall.md");
+
+        let cfg = build_with_settings(&["show"], &[], settings)?;
+        assert_eq!(cfg.file, "personal.md");
+        Ok(())
+    }
+
+    #[test]
+    fn config_fails_for_unknown_list() {
+        assert!(build_with_settings(&["-l", "team", "show"], &[], "").is_err());
+        assert!(build_with_settings(&["show"], &[], "[lists]\ndefault = \"x.md\"").is_err());
+        assert!(build(&["-l", "team", "-f", "team.md", "show"], &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn config_fails_for_invalid_settings_file() {
+        assert!(build_with_settings(&["show"], &[], "unknown = true").is_err());
+        assert!(build_with_settings(&["show"], &[], "[theme]\nsection = \"sky\"").is_err());
+        assert!(build_with_settings(&["show"], &[], "format = \"html\"").is_err());
+    }
+
+    #[test]
+    fn config_prefers_file_flag_over_env() -> Result<()> {
+        let env_vars = HashMap::from([("FRIDAY_FILE".to_string(), "from-env".to_string())]);
+        let cfg = build(&["show", "--file", "from-flag"], &env_vars)?;
+        assert_eq!(cfg.file, "from-flag");
         Ok(())
     }
 
     #[test]
     fn config_fails_for_invalid_enum() {
-        let args = vec!["binary".to_string(), "invalid".to_string()];
-        let cfg = Config::build(args.into_iter(), &HashMap::new());
-        assert!(cfg.is_err());
+        assert!(build(&["invalid"], &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn config_parses_filter_flags() -> Result<()> {
+        let args = ["show", "--tag", "infra", "--person", "anna", "--tag", "backend"];
+        let cfg = build(&args, &HashMap::new())?;
+        let Some(Action::Show(show_args)) = cfg.action else {
+            panic!("expected show action, got {:?}", cfg.action);
+        };
+        assert_eq!(
+            Query::from(show_args),
+            Query {
+                tags: vec!["infra".to_string(), "backend".to_string()],
+                people: vec!["anna".to_string()],
+                ..Query::default()
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn config_parses_section_flag() -> Result<()> {
+        let cfg = build(&["done", "--section", "todo", "update", "deps"], &HashMap::new())?;
+        assert_eq!(
+            cfg.action,
+            Some(Action::Done {
+                pattern: vec!["update".to_string(), "deps".to_string()],
+                section: Some("todo".to_string())
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn config_parses_due_and_sort_flags() -> Result<()> {
+        let cfg = build(&["show", "--due", "this-week", "--sort", "priority"], &HashMap::new())?;
+        let Some(Action::Show(show_args)) = cfg.action else {
+            panic!("expected show action, got {:?}", cfg.action);
+        };
+        assert_eq!(show_args.due, Some(DueFilter::ThisWeek));
+        assert_eq!(show_args.sort, Some(Sort::Priority));
+        Ok(())
+    }
+
+    #[test]
+    fn config_reads_user() -> Result<()> {
+        let env_vars = HashMap::from([("USER".to_string(), "tobi".to_string())]);
+        let cfg = build(&["show", "--mine"], &env_vars)?;
+        assert_eq!(cfg.user.as_deref(), Some("tobi"));
+        let Some(Action::Show(show_args)) = cfg.action else {
+            panic!("expected show action, got {:?}", cfg.action);
+        };
+        assert!(show_args.mine);
+        Ok(())
+    }
+
+    #[test]
+    fn config_parses_format_flag() -> Result<()> {
+        let cfg = build(&["show", "--format", "json"], &HashMap::new())?;
+        let Some(Action::Show(show_args)) = cfg.action else {
+            panic!("expected show action, got {:?}", cfg.action);
+        };
+        assert_eq!(show_args.format, Some(Format::Json));
+        assert_eq!(cfg.format, Format::Json);
+        Ok(())
+    }
+
+    #[test]
+    fn config_joins_find_query() -> Result<()> {
+        let cfg = build(&["find", "upd", "deps"], &HashMap::new())?;
+        assert_eq!(cfg.action, Some(Action::Find { query: vec!["upd".into(), "deps".into()] }));
+        Ok(())
+    }
+
+    #[test]
+    fn config_fails_for_missing_flag_value() {
+        assert!(build(&["show", "--tag"], &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn config_fails_for_invalid_due_filter() {
+        assert!(build(&["show", "--due", "someday"], &HashMap::new()).is_err());
     }
 }
@@ -6,12 +6,38 @@ use std::{convert, io};
 pub enum Error {
     #[error("Invalid argument: {0}")]
     InvalidArgument(String),
-    #[error("Invalid command: {0}")]
-    InvalidCommand(String),
     #[error("IO error: {0}")]
     IO(io::Error),
     #[error("{0}")]
     Core(friday_core::Error),
+    #[error("Failed to format output: {0}")]
+    Format(String),
+}
+
+impl Error {
+    /// The exit code of the process when failing with this error. Usage
+    /// errors use 2 like clap does.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::InvalidArgument(_) => 2,
+            Error::Core(e) => core_exit_code(e),
+            Error::IO(_) | Error::Format(_) => 1,
+        }
+    }
+}
+
+// failed batches exit like their failed operation
+fn core_exit_code(e: &friday_core::Error) -> i32 {
+    match e {
+        friday_core::Error::InvalidArgument(_)
+        | friday_core::Error::InvalidCommand(_)
+        | friday_core::Error::EmptyTask
+        | friday_core::Error::TaskTooLong(_) => 2,
+        friday_core::Error::SectionNotFound(_) | friday_core::Error::TaskNotFound(_) => 3,
+        friday_core::Error::AmbiguousMatch { .. } => 4,
+        friday_core::Error::BatchFailed { source, .. } => core_exit_code(source),
+        friday_core::Error::IO(_) => 1,
+    }
 }
 
 impl convert::From<io::Error> for Error {
@@ -26,5 +52,23 @@ impl convert::From<friday_core::Error> for Error {
     }
 }
 
+impl convert::From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error::Format(err.to_string())
+    }
+}
+
+impl convert::From<serde_yaml::Error> for Error {
+    fn from(err: serde_yaml::Error) -> Self {
+        Error::Format(err.to_string())
+    }
+}
+
+impl convert::From<toml::ser::Error> for Error {
+    fn from(err: toml::ser::Error) -> Self {
+        Error::Format(err.to_string())
+    }
+}
+
 // this pattern can also be found in ripgrep and anyhow
 pub type Result<T> = std::result::Result<T, Error>;
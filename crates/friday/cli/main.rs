@@ -1,7 +1,11 @@
 #![warn(clippy::pedantic)]
 
+mod batch;
+mod choose;
 mod config;
 mod error;
+mod remote;
+mod tui;
 
 use std::{
     env,
@@ -9,12 +13,15 @@ use std::{
     process::{exit, Command},
 };
 
-use config::{Action, Config};
+use chrono::Local;
+use clap::{CommandFactory, Parser};
+use config::{Action, Cli, Config, Format, Settings, Theme};
+use crossterm::tty::IsTty;
 use error::{Error, Result};
-use friday_core::{DefaultManager, FileBacked, Manager};
+use friday_core::{DefaultManager, FileBacked, Manager, Query};
 use owo_colors::OwoColorize;
+use remote::Remote;
 
-const DEFAULT_EDITOR: &str = "vi";
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 // see here: https://doc.rust-lang.org/book/ch09-02-recoverable-errors-with-result.html
@@ -22,51 +29,164 @@ fn main() {
     // see https://github.com/rust-lang/log
     env_logger::init_from_env(env_logger::Env::default().default_filter_or("INFO"));
 
-    let cfg = Config::build(env::args(), &env::vars().collect()).unwrap_or_else(|e| {
+    let cfg = Config::build(Cli::parse(), &env::vars().collect()).unwrap_or_else(|e| {
         eprintln!("failed to load options: {e}");
         exit(1)
     });
 
     run(cfg).unwrap_or_else(|e| {
-        eprint!("error during run: {e}");
-        exit(1)
+        eprintln!("error during run: {e}");
+        if let Error::Core(friday_core::Error::AmbiguousMatch { candidates, .. }) = &e {
+            let _ = choose::list(candidates, io::stderr());
+            eprintln!("use the id of one of them as pattern to select it");
+        }
+        exit(e.exit_code())
     });
 }
 
-fn run(cfg: Config) -> Result<()> {
+fn run(mut cfg: Config) -> Result<()> {
     log::debug!("running with config '{:?}'", cfg);
-    let repo = FileBacked::new(&cfg.file)?;
-    let manager = DefaultManager::new(repo);
-
-    match cfg.action {
-        Action::Add => add(&manager, cfg.input.unwrap_or_default().as_str()),
-        Action::Show => show(&manager),
-        Action::Edit => edit_file(&cfg.file),
-        Action::Help => print_help(),
+    let Some(action) = cfg.action.take() else {
+        Cli::command().print_help()?;
+        return Ok(());
+    };
+
+    if let Some(url) = cfg.remote.clone() {
+        return run_action(&Remote::new(&url, &cfg.list, cfg.token.clone()), action, cfg);
+    }
+    let repo = FileBacked::new(cfg.file.clone())?;
+    run_action(&DefaultManager::new(repo), action, cfg)
+}
+
+fn run_action(manager: &impl Manager, action: Action, cfg: Config) -> Result<()> {
+    match action {
+        Action::Add { task, section } => add(
+            manager,
+            &task.join(" "),
+            Some(&section.unwrap_or(cfg.section)),
+            cfg.user.as_deref(),
+        ),
+        Action::Show(args) => {
+            let mine = args.mine;
+            let mut query = Query::from(args);
+            if mine {
+                let user = cfg.user.clone().ok_or_else(|| {
+                    Error::InvalidArgument("--mine requires $USER to be set".to_string())
+                })?;
+                query.assignee = Some(user);
+            }
+            show(manager, &query, cfg.format, &cfg.theme)
+        }
+        Action::Done { pattern, section } => {
+            done(manager, &pattern.join(" "), Some(&section.unwrap_or(cfg.section)))
+        }
+        Action::Rm { pattern, section } => {
+            let section = section.unwrap_or(cfg.section);
+            with_choice(&pattern.join(" "), |p| manager.rm(p, Some(&section)))
+        }
+        Action::Batch => {
+            let ops = batch::parse(io::stdin().lock(), &cfg.section)?;
+            manager.apply(&ops, cfg.user.as_deref())?;
+            Ok(())
+        }
+        Action::Find { query } => find(manager, &query.join(" "), &cfg.theme),
+        Action::Rollover => rollover(manager),
+        Action::NewWeek => {
+            manager.new_week()?;
+            Ok(())
+        }
+        Action::Edit if cfg.remote.is_some() => {
+            Err(Error::InvalidArgument("remote lists can't be edited in $EDITOR".to_string()))
+        }
+        Action::Edit => edit_file(&cfg.editor, &cfg.file),
+        Action::Tui => tui::run(manager, cfg.user.as_deref()),
         Action::Version => {
             println!("{VERSION}");
             Ok(())
         }
+        Action::Config => {
+            print!("{}", toml::to_string(&Settings::from(&cfg))?);
+            Ok(())
+        }
+        Action::Lists => {
+            for (name, file) in &cfg.lists {
+                let marker = if *name == cfg.list { "*" } else { " " };
+                println!("{marker} {name}\t{file}");
+            }
+            Ok(())
+        }
+        Action::Completions { shell } => {
+            clap_complete::generate(shell, &mut Cli::command(), "friday", &mut io::stdout());
+            Ok(())
+        }
     }
 }
 
-fn add(manager: &impl Manager, input: &str) -> Result<()> {
-    manager.add(input, None)?;
+fn add(
+    manager: &impl Manager,
+    input: &str,
+    section: Option<&str>,
+    author: Option<&str>,
+) -> Result<()> {
+    manager.add(input, section, author)?;
     Ok(())
 }
 
-fn edit_file(path: &str) -> Result<()> {
-    let mut editor = env::var("EDITOR").unwrap_or_default();
-    // also handles case where EDITOR is set to "" explictly
-    if editor.trim().is_empty() {
-        log::debug!("resetting EDITOR to default");
-        editor = DEFAULT_EDITOR.to_string();
+fn done(manager: &impl Manager, pattern: &str, section: Option<&str>) -> Result<()> {
+    with_choice(pattern, |p| manager.complete(p, section))
+}
+
+// with_choice runs the operation for the task matching the pattern. If more
+// than one task matches and friday runs interactively, the user chooses one of
+// them and the operation is run again with its id.
+fn with_choice(
+    pattern: &str,
+    op: impl Fn(&str) -> std::result::Result<(), friday_core::Error>,
+) -> Result<()> {
+    match op(pattern) {
+        Err(friday_core::Error::AmbiguousMatch { pattern, candidates }) if io::stdin().is_tty() => {
+            eprintln!("pattern {pattern} matches more than one task");
+            let candidate = choose::choose(&candidates, io::stdin().lock(), io::stderr())?;
+            Ok(op(&candidate.id)?)
+        }
+        res => Ok(res?),
+    }
+}
+
+fn find(manager: &impl Manager, query: &str, theme: &Theme) -> Result<()> {
+    for m in manager.search(query)? {
+        let section = format!("{}:", m.section);
+        let task: String = m
+            .task
+            .text
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                if m.positions.contains(&i) {
+                    c.bold().color(theme.highlight.color).to_string()
+                } else {
+                    c.to_string()
+                }
+            })
+            .collect();
+        println!("{} {task}", section.color(theme.section.color));
     }
+    Ok(())
+}
 
+fn rollover(manager: &impl Manager) -> Result<()> {
+    for task in manager.rollover()? {
+        println!("added {task}");
+    }
+    Ok(())
+}
+
+fn edit_file(editor: &str, path: &str) -> Result<()> {
     // in case the editor env var contains args like e.g. `code -w`
     // it's necessary to split it up into program and args.
     let mut editor_parts = editor.split_whitespace();
-    // since editor is checked before, it will have at least one part
+    // since the config never contains an empty editor, it will have at least
+    // one part
     let mut cmd = Command::new(editor_parts.next().unwrap());
     // use rest of parts as args
     cmd.args(editor_parts);
@@ -83,37 +203,37 @@ fn edit_file(path: &str) -> Result<()> {
     Ok(())
 }
 
-#[allow(clippy::unnecessary_wraps)] // easier to use in run
-fn show(manager: &impl Manager) -> Result<()> {
-    let sections = manager.sections()?;
-    for (section, tasks) in sections {
-        let section_header = format!("## {section}");
-        let section_header = section_header.cyan();
-        println!("{section_header}");
-
-        for task in tasks {
-            println!("- {task}",);
+fn show(manager: &impl Manager, query: &Query, format: Format, theme: &Theme) -> Result<()> {
+    let sections = manager.sections(query)?;
+    match format {
+        Format::Json => println!("{}", serde_json::to_string_pretty(&sections)?),
+        Format::Yaml => print!("{}", serde_yaml::to_string(&sections)?),
+        Format::Plain => {
+            for (section, tasks) in sections {
+                for task in tasks {
+                    println!("{section}\t{task}");
+                }
+            }
+        }
+        Format::Markdown => {
+            let today = Local::now().date_naive();
+            for (section, tasks) in sections {
+                let section_header = format!("## {section}");
+                let section_header = section_header.color(theme.section.color);
+                println!("{section_header}");
+
+                for task in tasks {
+                    let line = format!("- {task}");
+                    if task.is_overdue(today) {
+                        println!("{}", line.color(theme.overdue.color));
+                    } else {
+                        println!("{line}");
+                    }
+                }
+
+                println!();
+            }
         }
-
-        println!();
     }
     Ok(())
 }
-
-#[allow(clippy::unnecessary_wraps)] // easier to use in run
-fn print_help() -> Result<()> {
-    println!(
-        "\
-This binary let's you manage stuff to do on fridays.
-
-The following commands are available:
-    help            -> Print this help text.
-    add <string>    -> Add a string to the end of the file.
-    show            -> Show the contents of the file.
-
-The location of the file that should be used can be configured
-globally using the `FRIDAY_FILE` env var.
-"
-    );
-    Ok(())
-}
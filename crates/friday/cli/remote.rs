@@ -0,0 +1,308 @@
+use std::io;
+
+use friday_client::{Batch, CreateTask, ListResponse, Problem, NEXT_CURSOR_HEADER};
+use friday_core::{Candidate, Error, Manager, Match, Op, Query, Section, Task};
+use indexmap::IndexMap;
+use serde::de::DeserializeOwned;
+
+use crate::config::DEFAULT_LIST;
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// Remote is a [`Manager`] that forwards everything to the routes of a
+/// `fridaypi` server. Only the operations the API supports are available.
+/// Added tasks are attributed to the user of the token by the server, the
+/// author passed to the manager is ignored.
+pub struct Remote {
+    /// the url of the server including the path to the list, e.g.
+    /// `http://localhost:3000/lists/team`
+    base: String,
+    /// sent as bearer token if set
+    token: Option<String>,
+    agent: ureq::Agent,
+}
+
+impl Remote {
+    pub fn new(url: &str, list: &str, token: Option<String>) -> Self {
+        let mut base = url.trim_end_matches('/').to_string();
+        if list != DEFAULT_LIST {
+            base = format!("{base}/lists/{}", encode(list));
+        }
+        Remote { base, token, agent: ureq::Agent::new() }
+    }
+
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        let req = self.agent.request(method, url);
+        match &self.token {
+            Some(token) => req.set("Authorization", &format!("Bearer {token}")),
+            None => req,
+        }
+    }
+
+    fn tasks_url(&self, section: Option<&str>) -> String {
+        let section: Section = section.into();
+        format!("{}/tasks/{}", self.base, encode(&section.to_string()))
+    }
+
+    // single applies a batch of one operation, since moving and completing are
+    // only available as operations of a batch. Errors are reported like for the
+    // single operations of local lists.
+    fn single(&self, op: Op) -> Result<()> {
+        self.apply(&[op], None).map_err(|e| match e {
+            Error::BatchFailed { source, .. } => *source,
+            e => e,
+        })
+    }
+
+    // get requests the page of tasks matching the query that starts at the
+    // cursor. The cursor of the next page is returned if it's set as header.
+    fn get<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        query: &Query,
+        cursor: Option<&str>,
+    ) -> Result<(T, Option<String>)> {
+        let mut req = self.request("GET", url);
+        let tags = query.tags.join(",");
+        let people = query.people.join(",");
+        let due = query.due.map(|d| d.to_string());
+        let sort = query.sort.map(|s| s.to_string());
+        let cursor = cursor.map(String::from);
+        let params = [
+            ("tag", Some(tags)),
+            ("person", Some(people)),
+            ("assignee", query.assignee.clone()),
+            ("due", due),
+            ("sort", sort),
+            ("cursor", cursor),
+        ];
+        for (key, value) in params {
+            if let Some(value) = value.filter(|v| !v.is_empty()) {
+                req = req.query(key, &value);
+            }
+        }
+        let res = req.call();
+        let next = res.as_ref().ok().and_then(|r| r.header(NEXT_CURSOR_HEADER)).map(String::from);
+        Ok((parse(res)?, next))
+    }
+}
+
+impl Manager for Remote {
+    fn add(&self, task: &str, section: Option<&str>, _: Option<&str>) -> Result<Task> {
+        if task.trim().is_empty() {
+            return Err(Error::EmptyTask);
+        }
+        let res = self
+            .request("POST", &self.tasks_url(section))
+            .send_json(CreateTask { task: task.to_string() });
+        // the server responds with all sections, new tasks are appended
+        let mut sections: IndexMap<Section, Vec<Task>> = parse(res)?;
+        sections
+            .remove(&Section::from(section))
+            .and_then(|mut tasks| tasks.pop())
+            .ok_or_else(|| other("task wasn't added".to_string()))
+    }
+
+    fn list(&self, section: Option<&str>, query: &Query) -> Result<Vec<Task>> {
+        let url = self.tasks_url(section);
+        let mut tasks = vec![];
+        let mut cursor = None;
+        loop {
+            let (page, _): (ListResponse<Task>, _) = self.get(&url, query, cursor.as_deref())?;
+            tasks.extend(page.items);
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                return Ok(tasks);
+            }
+        }
+    }
+
+    fn sections(&self, query: &Query) -> Result<IndexMap<Section, Vec<Task>>> {
+        let url = format!("{}/tasks", self.base);
+        let mut sections: IndexMap<Section, Vec<Task>> = IndexMap::new();
+        let mut cursor = None;
+        loop {
+            let (page, next): (IndexMap<Section, Vec<Task>>, _) =
+                self.get(&url, query, cursor.as_deref())?;
+            cursor = next;
+            for (section, tasks) in page {
+                sections.entry(section).or_default().extend(tasks);
+            }
+            if cursor.is_none() {
+                return Ok(sections);
+            }
+        }
+    }
+
+    fn search(&self, query: &str) -> Result<Vec<Match>> {
+        let req = self.request("GET", &format!("{}/search", self.base)).query("q", query);
+        let res: ListResponse<Match> = parse(req.call())?;
+        Ok(res.items)
+    }
+
+    // the server lists the tasks matching an ambiguous pattern, so that one can
+    // be chosen like for local lists
+    fn rm(&self, pattern: &str, section: Option<&str>) -> Result<()> {
+        let req = self.request("DELETE", &self.tasks_url(section)).query("pattern", pattern);
+        parse::<serde::de::IgnoredAny>(req.call()).map(|_| ())
+    }
+
+    fn mv(&self, pattern: &str, from: Option<&str>, to: Option<&str>) -> Result<()> {
+        let op = Op::Mv {
+            pattern: pattern.to_string(),
+            from: from.map(String::from),
+            to: to.map(String::from),
+        };
+        self.single(op)
+    }
+
+    fn complete(&self, pattern: &str, section: Option<&str>) -> Result<()> {
+        let op = Op::Complete { pattern: pattern.to_string(), section: section.map(String::from) };
+        self.single(op)
+    }
+
+    fn rollover(&self) -> Result<Vec<Task>> {
+        Err(unsupported("rollover"))
+    }
+
+    fn new_week(&self) -> Result<()> {
+        Err(unsupported("starting a new week"))
+    }
+
+    fn apply(&self, ops: &[Op], _: Option<&str>) -> Result<()> {
+        let res = self
+            .request("POST", &format!("{}/tasks:batch", self.base))
+            .send_json(Batch { ops: ops.to_vec() });
+        parse::<serde::de::IgnoredAny>(res).map(|_| ())
+    }
+
+    // the server is ready if the list can be read
+    fn check(&self) -> Result<()> {
+        let res = self.request("GET", &format!("{}/tasks", self.base)).query("limit", "1").call();
+        parse::<serde::de::IgnoredAny>(res).map(|_| ())
+    }
+}
+
+// parse reads the JSON body of a successful response. Problems returned by the
+// server are turned back into the errors of the manager, so that they're
+// reported like for local lists.
+fn parse<T: DeserializeOwned>(res: std::result::Result<ureq::Response, ureq::Error>) -> Result<T> {
+    match res {
+        Ok(res) => res.into_json().map_err(Error::from),
+        Err(ureq::Error::Status(status, res)) => Err(match res.into_json::<Problem>() {
+            Ok(problem) => from_problem(problem),
+            Err(_) if (400..500).contains(&status) => {
+                Error::InvalidArgument(format!("request failed with status {status}"))
+            }
+            Err(_) => other(format!("request failed with status {status}")),
+        }),
+        Err(e) => Err(other(e.to_string())),
+    }
+}
+
+// from_problem reverses the mapping of errors to problems by the server. Other
+// client errors are considered invalid arguments.
+fn from_problem(problem: Problem) -> Error {
+    let kind = problem.kind.strip_prefix("urn:friday:problem:").unwrap_or_default();
+    let error = match (kind, problem.pattern, problem.section, problem.max_length) {
+        ("task-not-found", Some(pattern), ..) => Error::TaskNotFound(pattern),
+        ("section-not-found", _, Some(section), _) => {
+            Error::SectionNotFound(Some(section.as_str()).into())
+        }
+        ("ambiguous-match", Some(pattern), ..) => {
+            let candidates = problem
+                .candidates
+                .into_iter()
+                .map(|c| Candidate {
+                    section: Some(c.section.as_str()).into(),
+                    id: c.id,
+                    task: c.task,
+                })
+                .collect();
+            Error::AmbiguousMatch { pattern, candidates }
+        }
+        ("too-large", _, _, Some(max)) => Error::TaskTooLong(max),
+        _ if (400..500).contains(&problem.status) => Error::InvalidArgument(problem.detail),
+        _ => other(problem.detail),
+    };
+    match problem.operation {
+        Some(index) => Error::BatchFailed { index, source: Box::new(error) },
+        None => error,
+    }
+}
+
+fn other(message: String) -> Error {
+    Error::IO(io::Error::new(io::ErrorKind::Other, message))
+}
+
+fn unsupported(operation: &str) -> Error {
+    Error::InvalidCommand(format!("{operation} is not supported for remote lists"))
+}
+
+// encode percent-encodes a path segment, since section and list names may
+// contain spaces or slashes.
+fn encode(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            b => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remote_builds_urls() {
+        let remote = Remote::new("http://localhost:3000/", DEFAULT_LIST, None);
+        assert_eq!(remote.tasks_url(None), "http://localhost:3000/tasks/dump");
+
+        let remote = Remote::new("http://localhost:3000", "on call", None);
+        assert_eq!(
+            remote.tasks_url(Some("to do/now")),
+            "http://localhost:3000/lists/on%20call/tasks/to%20do%2Fnow"
+        );
+    }
+
+    fn problem(kind: &str, status: u16) -> Problem {
+        Problem {
+            kind: format!("urn:friday:problem:{kind}"),
+            title: String::new(),
+            status,
+            detail: "some detail".to_string(),
+            candidates: vec![],
+            pattern: None,
+            section: None,
+            max_length: None,
+            operation: None,
+        }
+    }
+
+    #[test]
+    fn from_problem_restores_errors() {
+        let err = from_problem(Problem {
+            pattern: Some("deps".to_string()),
+            operation: Some(1),
+            ..problem("task-not-found", 404)
+        });
+        let Error::BatchFailed { index: 1, source } = err else { panic!("expected a failed batch") };
+        assert!(matches!(*source, Error::TaskNotFound(ref p) if p == "deps"));
+
+        let section = Some("todo".to_string());
+        let err = from_problem(Problem { section, ..problem("section-not-found", 404) });
+        assert!(matches!(err, Error::SectionNotFound(Section::Custom(ref s)) if s == "todo"));
+
+        let err = from_problem(Problem { max_length: Some(10), ..problem("too-large", 413) });
+        assert!(matches!(err, Error::TaskTooLong(10)));
+
+        // bodies that are too large aren't errors of the manager
+        let err = from_problem(problem("too-large", 413));
+        assert!(matches!(err, Error::InvalidArgument(ref d) if d == "some detail"));
+        assert!(matches!(from_problem(problem("internal", 500)), Error::IO(_)));
+    }
+}
@@ -0,0 +1,323 @@
+use std::io;
+
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use friday_core::{Manager, Query, Section, Task};
+use indexmap::IndexMap;
+use ratatui::{
+    backend::{Backend, CrosstermBackend},
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+    Terminal,
+};
+
+use crate::Result;
+
+const HELP: &str = "j/k: select task  h/l: select section  a: add  x: done  d: delete  m: move  \
+                    q: quit";
+
+/// Starts the interactive mode. Every change is written through the manager
+/// right away, so there's nothing to save when quitting. Added tasks are
+/// attributed to the author.
+pub fn run(manager: &impl Manager, author: Option<&str>) -> Result<()> {
+    let mut app = App::new(manager, author)?;
+
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+    let res = event_loop(&mut terminal, &mut app);
+
+    // restore the terminal even if the loop failed
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+    res
+}
+
+fn event_loop<B: Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App<'_, impl Manager>,
+) -> Result<()> {
+    loop {
+        terminal.draw(|f| draw(f, app))?;
+        if let Event::Key(key) = event::read()? {
+            // on windows both press and release events are reported
+            if key.kind == KeyEventKind::Press && app.handle_key(key)? {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Mode {
+    Normal,
+    /// typing a new task for the selected section
+    Add(String),
+    /// typing the section to move the selected task to
+    Move(String),
+}
+
+struct App<'a, M: Manager> {
+    manager: &'a M,
+    author: Option<&'a str>,
+    sections: IndexMap<Section, Vec<Task>>,
+    section: usize,
+    tasks: ListState,
+    mode: Mode,
+    status: String,
+}
+
+impl<'a, M: Manager> App<'a, M> {
+    fn new(manager: &'a M, author: Option<&'a str>) -> Result<Self> {
+        let mut app = Self {
+            manager,
+            author,
+            sections: IndexMap::new(),
+            section: 0,
+            tasks: ListState::default(),
+            mode: Mode::Normal,
+            status: String::new(),
+        };
+        app.reload()?;
+        Ok(app)
+    }
+
+    fn reload(&mut self) -> Result<()> {
+        self.sections = self.manager.sections(&Query::default())?;
+        self.section = self.section.min(self.sections.len().saturating_sub(1));
+        self.select_task(self.tasks.selected().unwrap_or_default());
+        Ok(())
+    }
+
+    fn selected_section(&self) -> Option<&Section> {
+        self.sections.get_index(self.section).map(|(section, _)| section)
+    }
+
+    fn selected_tasks(&self) -> &[Task] {
+        self.sections.get_index(self.section).map_or(&[], |(_, tasks)| tasks)
+    }
+
+    fn selected_task(&self) -> Option<&Task> {
+        self.selected_tasks().get(self.tasks.selected()?)
+    }
+
+    // select_task selects the task at the index, clamped to the tasks of the
+    // current section.
+    fn select_task(&mut self, index: usize) {
+        let len = self.selected_tasks().len();
+        self.tasks.select(if len == 0 { None } else { Some(index.min(len - 1)) });
+    }
+
+    fn select_section(&mut self, index: usize) {
+        if self.sections.is_empty() {
+            return;
+        }
+        self.section = index % self.sections.len();
+        self.select_task(0);
+    }
+
+    /// Handles a key press, returns true if the app should quit.
+    fn handle_key(&mut self, key: KeyEvent) -> Result<bool> {
+        match &mut self.mode {
+            Mode::Normal => return self.handle_normal_key(key.code),
+            Mode::Add(input) | Mode::Move(input) => match key.code {
+                KeyCode::Char(c) => input.push(c),
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Esc => self.mode = Mode::Normal,
+                KeyCode::Enter => {
+                    let mode = std::mem::replace(&mut self.mode, Mode::Normal);
+                    self.submit(mode)?;
+                }
+                _ => {}
+            },
+        }
+        Ok(false)
+    }
+
+    fn handle_normal_key(&mut self, code: KeyCode) -> Result<bool> {
+        let task = self.tasks.selected().unwrap_or_default();
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(true),
+            KeyCode::Char('j') | KeyCode::Down => self.select_task(task + 1),
+            KeyCode::Char('k') | KeyCode::Up => self.select_task(task.saturating_sub(1)),
+            KeyCode::Char('l') | KeyCode::Right | KeyCode::Tab => {
+                self.select_section(self.section + 1);
+            }
+            KeyCode::Char('h') | KeyCode::Left | KeyCode::BackTab => {
+                self.select_section(self.section + self.sections.len().saturating_sub(1));
+            }
+            KeyCode::Char('a') => self.mode = Mode::Add(String::new()),
+            KeyCode::Char('m') if self.selected_task().is_some() => {
+                self.mode = Mode::Move(String::new());
+            }
+            KeyCode::Char('x' | ' ') => self.on_selected(|m, task, section| {
+                m.complete(task, section).map(|()| format!("completed {task}"))
+            })?,
+            KeyCode::Char('d') => self.on_selected(|m, task, section| {
+                m.rm(task, section).map(|()| format!("deleted {task}"))
+            })?,
+            _ => {}
+        }
+        Ok(false)
+    }
+
+    fn submit(&mut self, mode: Mode) -> Result<()> {
+        match mode {
+            Mode::Add(input) if !input.trim().is_empty() => {
+                let section = self.selected_section().map(ToString::to_string);
+                self.status = match self.manager.add(&input, section.as_deref(), self.author) {
+                    Ok(task) => format!("added {task}"),
+                    Err(e) => e.to_string(),
+                };
+            }
+            Mode::Normal | Mode::Add(_) => {}
+            Mode::Move(to) => {
+                let to = to.trim().to_string();
+                self.on_selected(|m, task, section| {
+                    m.mv(task, section, Some(&to)).map(|()| format!("moved {task} to {to}"))
+                })?;
+            }
+        }
+        self.reload()
+    }
+
+    // on_selected runs the operation on the selected task and shows the
+    // outcome in the status line. Errors of the operation are shown instead of
+    // quitting the app.
+    fn on_selected<F>(&mut self, op: F) -> Result<()>
+    where
+        F: FnOnce(&M, &str, Option<&str>) -> std::result::Result<String, friday_core::Error>,
+    {
+        let (Some(section), Some(task)) = (self.selected_section(), self.selected_task()) else {
+            return Ok(());
+        };
+        self.status = match op(self.manager, &task.text, Some(&section.to_string())) {
+            Ok(status) => status,
+            Err(e) => e.to_string(),
+        };
+        self.reload()
+    }
+}
+
+fn draw<B: Backend>(f: &mut Frame<'_, B>, app: &mut App<'_, impl Manager>) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(3)])
+        .split(f.size());
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(25), Constraint::Percentage(75)])
+        .split(rows[0]);
+
+    let highlight = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+
+    let sections: Vec<ListItem> = app
+        .sections
+        .iter()
+        .map(|(section, tasks)| ListItem::new(format!("{section} ({})", tasks.len())))
+        .collect();
+    let mut section_state = ListState::default();
+    section_state.select(Some(app.section));
+    let sections = List::new(sections)
+        .block(Block::default().borders(Borders::ALL).title("sections"))
+        .highlight_style(highlight);
+    f.render_stateful_widget(sections, columns[0], &mut section_state);
+
+    let today = chrono::Local::now().date_naive();
+    let tasks: Vec<ListItem> = app
+        .selected_tasks()
+        .iter()
+        .map(|task| {
+            let item = ListItem::new(task.text.clone());
+            if task.done {
+                item.style(Style::default().fg(Color::DarkGray))
+            } else if task.is_overdue(today) {
+                item.style(Style::default().fg(Color::Red))
+            } else {
+                item
+            }
+        })
+        .collect();
+    let title = app.selected_section().map(ToString::to_string).unwrap_or_default();
+    let tasks = List::new(tasks)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(highlight)
+        .highlight_symbol("> ");
+    f.render_stateful_widget(tasks, columns[1], &mut app.tasks);
+
+    let (title, text) = match &app.mode {
+        Mode::Normal if app.status.is_empty() => ("help", HELP.to_string()),
+        Mode::Normal => ("status", app.status.clone()),
+        Mode::Add(input) => ("add task (enter: save, esc: cancel)", input.clone()),
+        Mode::Move(input) => ("move to section (enter: save, esc: cancel)", input.clone()),
+    };
+    let footer = Paragraph::new(text).block(Block::default().borders(Borders::ALL).title(title));
+    f.render_widget(footer, rows[1]);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crossterm::event::KeyModifiers;
+    use friday_core::{DefaultManager, FileBacked};
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn press(app: &mut App<'_, impl Manager>, keys: &str) {
+        for c in keys.chars() {
+            let code = match c {
+                '\n' => KeyCode::Enter,
+                c => KeyCode::Char(c),
+            };
+            app.handle_key(KeyEvent::new(code, KeyModifiers::NONE)).unwrap();
+        }
+    }
+
+    #[test]
+    fn tui_edits_tasks() -> Result<()> {
+        let dir = TempDir::new()?;
+        let file = dir.path().join("friday.md");
+        fs::write(&file, "## todo\n\n- first\n- second\n\n## dump\n\n- other\n")?;
+        let manager = DefaultManager::new(FileBacked::new(file.clone())?);
+        let mut app = App::new(&manager, None)?;
+
+        // complete the second task in todo, then move the first one to dump
+        press(&mut app, "jx");
+        press(&mut app, "kmdump\n");
+        // add a task to dump and delete the one that was there before
+        press(&mut app, "lanew task\n");
+        press(&mut app, "d");
+
+        let content = fs::read_to_string(&file)?;
+        let expected = "## todo\n\n- [x] second\n\n## dump\n\n- first\n- new task <!-- created:";
+        assert!(content.starts_with(expected), "got '{content}'");
+        Ok(())
+    }
+
+    #[test]
+    fn tui_quits() -> Result<()> {
+        let dir = TempDir::new()?;
+        let file = dir.path().join("friday.md");
+        let manager = DefaultManager::new(FileBacked::new(file.clone())?);
+        let mut app = App::new(&manager, None)?;
+
+        assert!(!app.handle_key(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE))?);
+        // q is part of the input while adding
+        assert!(!app.handle_key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE))?);
+        assert!(!app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE))?);
+        assert!(app.handle_key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE))?);
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+#![warn(clippy::pedantic)]
+
+//! Request and response types of the `fridaypi` API.
+//!
+//! The server uses the same types to build the spec it serves, so clients using
+//! them stay in sync with what is served at `/api.json`.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Body to add a task to a section.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq, Eq)]
+#[schemars(example = "example_create_task")]
+pub struct CreateTask {
+    /// the task including its annotations, e.g. `update deps due:friday`
+    pub task: String,
+}
+
+/// The header holding the number of tasks across all pages of `GET /tasks`.
+pub const TOTAL_COUNT_HEADER: &str = "x-total-count";
+/// The header holding the cursor of the next page of `GET /tasks`, it's
+/// missing on the last page.
+pub const NEXT_CURSOR_HEADER: &str = "x-next-cursor";
+
+/// Response of routes returning a list of items. Long lists are split into
+/// pages, the next one is requested by passing `next_cursor` as `cursor`.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq, Eq)]
+pub struct ListResponse<T> {
+    pub items: Vec<T>,
+    /// the number of items across all pages
+    pub total: usize,
+    /// the cursor of the next page, it's missing on the last page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+impl<T> From<Vec<T>> for ListResponse<T> {
+    fn from(items: Vec<T>) -> Self {
+        ListResponse { total: items.len(), items, next_cursor: None }
+    }
+}
+
+/// Body to run a batch of operations at once.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq, Eq)]
+pub struct Batch<T> {
+    pub ops: Vec<T>,
+}
+
+/// Body of all error responses, a problem details object as defined in
+/// [RFC 7807](https://www.rfc-editor.org/rfc/rfc7807). It's served with the
+/// `application/problem+json` content type.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq, Eq)]
+pub struct Problem {
+    /// identifies the kind of problem, e.g. `urn:friday:problem:task-not-found`
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// a short summary of the kind of problem
+    pub title: String,
+    /// the HTTP status code
+    pub status: u16,
+    /// an explanation specific to this occurrence of the problem
+    pub detail: String,
+    /// the tasks matching a pattern that matched more than one task
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub candidates: Vec<Candidate>,
+    /// the pattern that matched no or more than one task
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// the section that doesn't exist
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+    /// the maximum number of characters of a task that is too long
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+    /// the index of the failed operation of a batch, starting at 0
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operation: Option<usize>,
+}
+
+/// One of the tasks matching an ambiguous pattern, its id can be used to
+/// select it.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq, Eq)]
+pub struct Candidate {
+    /// the section the task is in
+    pub section: String,
+    pub id: String,
+    pub task: String,
+}
+
+fn example_create_task() -> CreateTask {
+    CreateTask { task: "update deps due:friday #infra".to_string() }
+}
@@ -0,0 +1,17 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// An operation of a batch, see [`Manager::apply`](crate::Manager::apply).
+/// Sections default to the dump section like for the single operations.
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum Op {
+    /// adds the task to the end of the section
+    Add { task: String, section: Option<String> },
+    /// removes the task matching the pattern
+    Rm { pattern: String, section: Option<String> },
+    /// moves the task matching the pattern to the end of another section
+    Mv { pattern: String, from: Option<String>, to: Option<String> },
+    /// checks off the task matching the pattern
+    Complete { pattern: String, section: Option<String> },
+}
@@ -0,0 +1,187 @@
+use core::fmt;
+use std::str;
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use schemars::JsonSchema;
+use serde_with::{DeserializeFromStr, SerializeDisplay};
+
+use crate::Error;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Parses the value of a `due:` annotation relative to `today`.
+///
+/// Supported values are ISO dates like `2026-10-23`, `today`, `tomorrow`,
+/// weekdays like `friday` (the next one, including today), as well as
+/// `this-friday` and `next-friday` which refer to the current and the
+/// following week.
+pub fn parse_date(value: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let value = value.to_lowercase();
+    match value.as_str() {
+        "today" => return Some(today),
+        "tomorrow" => return Some(today + Duration::days(1)),
+        _ => {}
+    }
+
+    if let Some(weekday) = value.strip_prefix("this-") {
+        return Some(start_of_week(today) + days_from_monday(weekday.parse().ok()?));
+    }
+    if let Some(weekday) = value.strip_prefix("next-") {
+        return Some(
+            start_of_week(today) + Duration::days(7) + days_from_monday(weekday.parse().ok()?),
+        );
+    }
+    if let Ok(weekday) = value.parse::<Weekday>() {
+        let days_until =
+            (7 + weekday.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7;
+        return Some(today + Duration::days(i64::from(days_until)));
+    }
+
+    NaiveDate::parse_from_str(&value, DATE_FORMAT).ok()
+}
+
+/// Replaces relative `due:` annotations like `due:next-friday` in a task with
+/// the absolute date, so that the task doesn't move around over time.
+pub fn resolve_dates(task: &str, today: NaiveDate) -> Result<String, Error> {
+    let mut words = Vec::new();
+    for word in task.split(' ') {
+        let Some(value) = word.strip_prefix("due:") else {
+            words.push(word.to_string());
+            continue;
+        };
+        let date = parse_date(value, today)
+            .ok_or_else(|| Error::InvalidArgument(format!("invalid due date {value}")))?;
+        words.push(format!("due:{}", date.format(DATE_FORMAT)));
+    }
+    Ok(words.join(" "))
+}
+
+fn start_of_week(date: NaiveDate) -> NaiveDate {
+    date - days_from_monday(date.weekday())
+}
+
+fn days_from_monday(weekday: Weekday) -> Duration {
+    Duration::days(i64::from(weekday.num_days_from_monday()))
+}
+
+/// `DueFilter` selects tasks by their due date.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, DeserializeFromStr, SerializeDisplay, JsonSchema)]
+pub enum DueFilter {
+    /// tasks that were due before today
+    Overdue,
+    /// tasks due today or earlier
+    Today,
+    /// tasks due until the end of the current week, including overdue ones
+    ThisWeek,
+    /// tasks due until the end of next week, including overdue ones
+    NextWeek,
+    /// tasks due on the given date or earlier
+    Until(NaiveDate),
+}
+
+impl DueFilter {
+    pub fn matches(&self, due: Option<NaiveDate>, today: NaiveDate) -> bool {
+        let Some(due) = due else {
+            return false;
+        };
+        let end_of_week = start_of_week(today) + Duration::days(6);
+        match self {
+            Self::Overdue => due < today,
+            Self::Today => due <= today,
+            Self::ThisWeek => due <= end_of_week,
+            Self::NextWeek => due <= end_of_week + Duration::days(7),
+            Self::Until(date) => due <= *date,
+        }
+    }
+}
+
+impl fmt::Display for DueFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Overdue => write!(f, "overdue"),
+            Self::Today => write!(f, "today"),
+            Self::ThisWeek => write!(f, "this-week"),
+            Self::NextWeek => write!(f, "next-week"),
+            Self::Until(date) => write!(f, "{}", date.format(DATE_FORMAT)),
+        }
+    }
+}
+
+impl str::FromStr for DueFilter {
+    type Err = Error;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "overdue" => Ok(Self::Overdue),
+            "today" => Ok(Self::Today),
+            "this-week" => Ok(Self::ThisWeek),
+            "next-week" => Ok(Self::NextWeek),
+            any => NaiveDate::parse_from_str(any, DATE_FORMAT).map(Self::Until).map_err(|_| {
+                Error::InvalidArgument(format!(
+                    "invalid due filter {any}, expected overdue, today, this-week, next-week or a \
+                     date"
+                ))
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2026-10-19 is a monday
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 19).unwrap()
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    macro_rules! test_parse_date {
+        ($name:ident, $in:expr => $out:expr) => {
+            #[test]
+            fn $name() {
+                assert_eq!(parse_date($in, today()), $out);
+            }
+        };
+    }
+
+    test_parse_date!(parse_iso_date, "2026-10-23" => Some(date(23)));
+    test_parse_date!(parse_today, "today" => Some(date(19)));
+    test_parse_date!(parse_tomorrow, "Tomorrow" => Some(date(20)));
+    test_parse_date!(parse_weekday, "friday" => Some(date(23)));
+    test_parse_date!(parse_weekday_today, "monday" => Some(date(19)));
+    test_parse_date!(parse_this_weekday, "this-friday" => Some(date(23)));
+    test_parse_date!(parse_next_weekday, "next-friday" => Some(date(30)));
+    test_parse_date!(parse_invalid, "someday" => None);
+
+    #[test]
+    fn resolve_dates_replaces_relative_dates() {
+        let task = resolve_dates("update deps due:next-friday #infra", today()).unwrap();
+        assert_eq!(task, "update deps due:2026-10-30 #infra");
+    }
+
+    #[test]
+    fn resolve_dates_fails_on_invalid_date() {
+        assert!(resolve_dates("update deps due:someday", today()).is_err());
+    }
+
+    #[test]
+    fn due_filter_matches() {
+        assert!(DueFilter::Overdue.matches(Some(date(18)), today()));
+        assert!(!DueFilter::Overdue.matches(Some(date(19)), today()));
+        assert!(DueFilter::ThisWeek.matches(Some(date(25)), today()));
+        assert!(!DueFilter::ThisWeek.matches(Some(date(26)), today()));
+        assert!(DueFilter::NextWeek.matches(Some(date(26)), today()));
+        assert!(!DueFilter::Today.matches(None, today()));
+    }
+
+    #[test]
+    fn due_filter_roundtrips() {
+        for filter in ["overdue", "today", "this-week", "next-week", "2026-10-23"] {
+            assert_eq!(filter.parse::<DueFilter>().unwrap().to_string(), filter);
+        }
+        assert!("someday".parse::<DueFilter>().is_err());
+    }
+}
@@ -1,5 +1,7 @@
 use std::{convert, io};
 
+use crate::Section;
+
 // See https://kerkour.com/rust-error-handling
 
 #[derive(thiserror::Error, Debug)]
@@ -8,10 +10,31 @@ pub enum Error {
     InvalidArgument(String),
     #[error("Invalid command: {0}")]
     InvalidCommand(String),
+    #[error("section {0} not found")]
+    SectionNotFound(Section),
+    #[error("no task matching {0} found")]
+    TaskNotFound(String),
+    #[error("pattern {pattern} matches {} tasks", candidates.len())]
+    AmbiguousMatch { pattern: String, candidates: Vec<Candidate> },
+    #[error("expected non-empty task")]
+    EmptyTask,
+    #[error("task exceeds the maximum length of {0} characters")]
+    TaskTooLong(usize),
+    #[error("operation {} failed: {source}", index + 1)]
+    BatchFailed { index: usize, source: Box<Error> },
     #[error("IO error: {0}")]
     IO(io::Error),
 }
 
+/// One of the tasks matching an ambiguous pattern. Its id can be used as the
+/// pattern to select it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Candidate {
+    pub section: Section,
+    pub id: String,
+    pub task: String,
+}
+
 impl convert::From<io::Error> for Error {
     fn from(err: io::Error) -> Self {
         Error::IO(err)
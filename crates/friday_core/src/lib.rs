@@ -2,12 +2,24 @@
 #![allow(clippy::must_use_candidate)]
 #![allow(clippy::missing_errors_doc)]
 
+mod batch;
+mod due;
 mod error;
 mod manager;
+mod query;
+mod recurrence;
 mod repo;
+mod search;
 mod section;
+mod task;
 
-pub use error::Error;
+pub use batch::Op;
+pub use due::{parse_date, resolve_dates, DueFilter};
+pub use error::{Candidate, Error};
 pub use manager::*;
+pub use query::*;
+pub use recurrence::*;
 pub use repo::*;
+pub use search::*;
 pub use section::*;
+pub use task::*;
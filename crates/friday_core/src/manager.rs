@@ -1,82 +1,360 @@
 use std::{
+    cmp,
     str,
     sync::{Arc, RwLock},
 };
 
+use chrono::{Local, NaiveDate, SubsecRound, Utc};
 use indexmap::IndexMap;
 
-use crate::{error::Result, Error, Repo, Section};
+use crate::{
+    due,
+    error::Result,
+    fuzzy_match,
+    task,
+    Candidate,
+    Error,
+    Match,
+    Op,
+    Query,
+    Repo,
+    Section,
+    Task,
+};
 
 pub trait Manager {
-    fn add(&self, task: &str, section: Option<&str>) -> Result<()>;
-    fn list(&self, section: Option<&str>) -> Result<Vec<String>>;
-    fn sections(&self) -> Result<IndexMap<Section, Vec<String>>>;
+    /// Adds the task to the end of the section and returns it as stored.
+    /// The author and the current time are stored with the task.
+    fn add(&self, task: &str, section: Option<&str>, author: Option<&str>) -> Result<Task>;
+    fn list(&self, section: Option<&str>, query: &Query) -> Result<Vec<Task>>;
+    /// Returns all sections with the tasks matching the query.
+    /// Sections without any matching task are omitted unless the query is
+    /// empty.
+    fn sections(&self, query: &Query) -> Result<IndexMap<Section, Vec<Task>>>;
+    /// Searches all sections for tasks fuzzily matching the query, see
+    /// [`fuzzy_match`]. The best matches come first.
+    fn search(&self, query: &str) -> Result<Vec<Match>>;
     fn rm(&self, pattern: &str, section: Option<&str>) -> Result<()>;
+    /// Moves the task matching the pattern to the end of another section.
+    fn mv(&self, pattern: &str, from: Option<&str>, to: Option<&str>) -> Result<()>;
+    /// Checks off the task matching the pattern. If the task recurs, the next
+    /// instance is added to the same section.
+    fn complete(&self, pattern: &str, section: Option<&str>) -> Result<()>;
+    /// Adds the next instance of every completed recurring task that doesn't
+    /// have an open instance yet. Returns the added tasks.
+    fn rollover(&self) -> Result<Vec<Task>>;
+    /// Archives the current week and starts a new one with all unfinished
+    /// tasks. Completed tasks are dropped, recurring ones are rolled over.
+    fn new_week(&self) -> Result<()>;
+    /// Runs all operations at once. If one of them fails, none of them is
+    /// applied. Added tasks are attributed to the author.
+    fn apply(&self, ops: &[Op], author: Option<&str>) -> Result<()>;
+    /// Returns an error if the tasks can't be read or written.
+    fn check(&self) -> Result<()>;
 }
 
 impl<T: Manager> Manager for Arc<RwLock<T>> {
-    fn add(&self, task: &str, section: Option<&str>) -> Result<()> {
-        self.write().unwrap().add(task, section)
+    fn add(&self, task: &str, section: Option<&str>, author: Option<&str>) -> Result<Task> {
+        self.write().unwrap().add(task, section, author)
+    }
+
+    fn list(&self, section: Option<&str>, query: &Query) -> Result<Vec<Task>> {
+        self.read().unwrap().list(section, query)
     }
 
-    fn list(&self, section: Option<&str>) -> Result<Vec<String>> {
-        self.read().unwrap().list(section)
+    fn sections(&self, query: &Query) -> Result<IndexMap<Section, Vec<Task>>> {
+        self.read().unwrap().sections(query)
     }
 
-    fn sections(&self) -> Result<IndexMap<Section, Vec<String>>> {
-        self.read().unwrap().sections()
+    fn search(&self, query: &str) -> Result<Vec<Match>> {
+        self.read().unwrap().search(query)
     }
 
     fn rm(&self, pattern: &str, section: Option<&str>) -> Result<()> {
         self.write().unwrap().rm(pattern, section)
     }
+
+    fn mv(&self, pattern: &str, from: Option<&str>, to: Option<&str>) -> Result<()> {
+        self.write().unwrap().mv(pattern, from, to)
+    }
+
+    fn complete(&self, pattern: &str, section: Option<&str>) -> Result<()> {
+        self.write().unwrap().complete(pattern, section)
+    }
+
+    fn rollover(&self) -> Result<Vec<Task>> {
+        self.write().unwrap().rollover()
+    }
+
+    fn new_week(&self) -> Result<()> {
+        self.write().unwrap().new_week()
+    }
+
+    fn apply(&self, ops: &[Op], author: Option<&str>) -> Result<()> {
+        self.write().unwrap().apply(ops, author)
+    }
+
+    fn check(&self) -> Result<()> {
+        self.read().unwrap().check()
+    }
 }
 
 pub struct DefaultManager<T: Repo> {
     repo: T,
+    /// the maximum number of characters of added tasks, unlimited if unset
+    max_task_len: Option<usize>,
 }
 
 impl<T: Repo> DefaultManager<T> {
     pub fn new(repo: T) -> DefaultManager<T> {
-        DefaultManager { repo }
+        DefaultManager { repo, max_task_len: None }
+    }
+
+    /// Rejects added tasks with more than `max` characters.
+    #[must_use]
+    pub fn with_max_task_len(mut self, max: usize) -> DefaultManager<T> {
+        self.max_task_len = Some(max);
+        self
+    }
+
+    // find returns the task as stored in the repo. Only the text is matched,
+    // not the metadata.
+    fn find(&self, pattern: &str, section: Option<&str>) -> Result<String> {
+        let tasks = self.repo.list(section.into())?;
+        let text = |t: &String| task::split_meta(t).0.to_string();
+        let mut matching_tasks: Vec<String> =
+            tasks.iter().filter(|t| text(t).contains(pattern)).cloned().collect();
+        // a task that matches exactly is preferred over the ones only containing
+        // the pattern, otherwise it could never be selected
+        if matching_tasks.iter().filter(|t| text(t) == pattern).count() == 1 {
+            matching_tasks.retain(|t| text(t) == pattern);
+        }
+        if matching_tasks.len() > 1 {
+            let candidates = matching_tasks
+                .iter()
+                .map(|t| Candidate { section: section.into(), id: task::id(t), task: text(t) })
+                .collect();
+            return Err(Error::AmbiguousMatch { pattern: pattern.to_string(), candidates });
+        }
+        if let Some(task) = matching_tasks.pop() {
+            return Ok(task);
+        }
+        // the ids of ambiguous matches can be used to select one of them
+        tasks
+            .into_iter()
+            .find(|t| task::id(t) == pattern)
+            .ok_or_else(|| Error::TaskNotFound(pattern.to_string()))
+    }
+
+    // carry_over archives all tasks and adds the open ones and the next
+    // instances of recurring ones again
+    fn carry_over(&self) -> Result<()> {
+        let today = today();
+        let sections = self.repo.list_all()?;
+        self.repo.archive(&today.to_string())?;
+
+        for (section, tasks) in sections {
+            let tasks: Vec<Task> = tasks.iter().map(|t| Task::parse_at(t, today)).collect();
+            let open = tasks.iter().filter(|t| !t.done).cloned();
+            for task in open.chain(next_instances(&tasks, today)) {
+                self.repo.create(&task.line(), section.clone())?;
+            }
+        }
+        Ok(())
     }
 }
 
 impl<T: Repo> Manager for DefaultManager<T> {
-    fn add(&self, task: &str, section: Option<&str>) -> Result<()> {
+    #[tracing::instrument(level = "debug", skip(self))]
+    fn add(&self, task: &str, section: Option<&str>, author: Option<&str>) -> Result<Task> {
         if task.trim().is_empty() {
-            return Err(Error::InvalidArgument("expected non-empty task".to_string()));
+            return Err(Error::EmptyTask);
+        }
+        if let Some(max) = self.max_task_len.filter(|max| task.chars().count() > *max) {
+            return Err(Error::TaskTooLong(max));
         }
 
-        self.repo.create(task, section.into())
+        let today = today();
+        let mut task = Task::parse_at(&due::resolve_dates(task, today)?, today);
+        task.author = author.map(String::from);
+        // the timestamp is stored in seconds
+        task.created = Some(Utc::now().trunc_subsecs(0));
+        self.repo.create(&task.line(), section.into())?;
+        Ok(task)
     }
 
-    fn sections(&self) -> Result<IndexMap<Section, Vec<String>>> {
-        self.repo.list_all()
+    #[tracing::instrument(level = "debug", skip(self))]
+    fn sections(&self, query: &Query) -> Result<IndexMap<Section, Vec<Task>>> {
+        let mut sections: IndexMap<Section, Vec<Task>> = self
+            .repo
+            .list_all()?
+            .into_iter()
+            .map(|(section, tasks)| (section, filter(&tasks, query)))
+            .collect();
+        if !query.is_empty() {
+            sections.retain(|_, tasks| !tasks.is_empty());
+        }
+        Ok(sections)
     }
 
-    fn list(&self, section: Option<&str>) -> Result<Vec<String>> {
-        self.repo.list(section.into())
+    #[tracing::instrument(level = "debug", skip(self))]
+    fn list(&self, section: Option<&str>, query: &Query) -> Result<Vec<Task>> {
+        Ok(filter(&self.repo.list(section.into())?, query))
     }
 
+    #[tracing::instrument(level = "debug", skip(self))]
+    fn search(&self, query: &str) -> Result<Vec<Match>> {
+        if query.trim().is_empty() {
+            return Err(Error::InvalidArgument("expected non-empty query".to_string()));
+        }
+
+        let today = today();
+        let mut matches = Vec::new();
+        for (section, tasks) in self.repo.list_all()? {
+            for task in tasks {
+                let Some((score, positions)) = fuzzy_match(task::split_meta(&task).0, query) else {
+                    continue;
+                };
+                let task = Task::parse_at(&task, today);
+                matches.push(Match { section: section.clone(), task, score, positions });
+            }
+        }
+        // stable, so equally good matches stay in file order
+        matches.sort_by_key(|m| cmp::Reverse(m.score));
+        Ok(matches)
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
     fn rm(&self, pattern: &str, section: Option<&str>) -> Result<()> {
-        let matching_tasks: Vec<String> =
-            self.repo.list(section.into())?.into_iter().filter(|t| t.contains(pattern)).collect();
-        if matching_tasks.len() > 1 {
-            return Err(Error::InvalidArgument(format!(
-                "found more than one match for pattern {pattern}"
-            )));
+        let task = self.find(pattern, section)?;
+        self.repo.delete(&task, section.into())
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    fn mv(&self, pattern: &str, from: Option<&str>, to: Option<&str>) -> Result<()> {
+        let task = self.find(pattern, from)?;
+        let to: Section = to.into();
+        if to == from.into() {
+            return Ok(());
+        }
+        self.repo.create(&task, to)?;
+        self.repo.delete(&task, from.into())
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    fn complete(&self, pattern: &str, section: Option<&str>) -> Result<()> {
+        let today = today();
+        let line = self.find(pattern, section)?;
+        let task = Task::parse_at(&line, today);
+        if task.done {
+            return Err(Error::InvalidArgument(format!("task {task} is already completed")));
+        }
+
+        self.repo.update(&line, &task.with_meta(&task.completed()), section.into())?;
+        if let Some(next) = task.next_instance(today) {
+            self.repo.create(&task.with_meta(&next), section.into())?;
         }
-        if matching_tasks.is_empty() {
-            return Err(Error::InvalidArgument(format!("no match found for pattern {pattern}")));
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    fn rollover(&self) -> Result<Vec<Task>> {
+        let today = today();
+        let mut added = Vec::new();
+        for (section, tasks) in self.repo.list_all()? {
+            let tasks: Vec<Task> = tasks.iter().map(|t| Task::parse_at(t, today)).collect();
+            for next in next_instances(&tasks, today) {
+                self.repo.create(&next.line(), section.clone())?;
+                added.push(next);
+            }
+        }
+        Ok(added)
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    fn new_week(&self) -> Result<()> {
+        self.repo.begin()?;
+        // the tasks are only removed from the file together with the archive
+        // being created and the carried over tasks being added again
+        if let Err(e) = self.carry_over() {
+            self.repo.rollback();
+            return Err(e);
+        }
+        self.repo.commit()
+    }
+
+    // the operations are staged in the repo, so the file is only read and
+    // written once for the whole batch.
+    #[tracing::instrument(level = "debug", skip(self, ops), fields(ops = ops.len()))]
+    fn apply(&self, ops: &[Op], author: Option<&str>) -> Result<()> {
+        self.repo.begin()?;
+        for (index, op) in ops.iter().enumerate() {
+            let res = match op {
+                Op::Add { task, section } => self.add(task, section.as_deref(), author).map(|_| ()),
+                Op::Rm { pattern, section } => self.rm(pattern, section.as_deref()),
+                Op::Mv { pattern, from, to } => self.mv(pattern, from.as_deref(), to.as_deref()),
+                Op::Complete { pattern, section } => self.complete(pattern, section.as_deref()),
+            };
+            if let Err(e) = res {
+                self.repo.rollback();
+                return Err(Error::BatchFailed { index, source: Box::new(e) });
+            }
+        }
+        self.repo.commit()
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    fn check(&self) -> Result<()> {
+        self.repo.check()
+    }
+}
+
+// next_instances returns the next instance of every completed recurring task
+// in a section that doesn't have an open instance yet. The instances keep the
+// metadata of the task.
+fn next_instances(tasks: &[Task], today: NaiveDate) -> Vec<Task> {
+    let mut next_instances: Vec<Task> = Vec::new();
+    for task in tasks.iter().filter(|t| t.done) {
+        let Some(next) = task.next_instance(today) else {
+            continue;
+        };
+        // an open instance might already exist if the task was completed
+        // with `complete` or the rollover already happened
+        let key = series(task);
+        let has_open_instance = tasks.iter().any(|t| !t.done && series(t) == key);
+        if has_open_instance || next_instances.iter().any(|t| series(t) == key) {
+            continue;
         }
-        self.repo.delete(matching_tasks.get(0).unwrap(), section.into())
+        next_instances.push(Task::parse_at(&task.with_meta(&next), today));
     }
+    next_instances
+}
+
+fn filter(tasks: &[String], query: &Query) -> Vec<Task> {
+    let today = today();
+    let mut tasks: Vec<Task> = tasks
+        .iter()
+        .map(|t| Task::parse_at(t, today))
+        .filter(|t| query.matches(t, today))
+        .collect();
+    query.sort(&mut tasks);
+    tasks
+}
+
+// series identifies all instances of a recurring task by ignoring the parts
+// that change between instances.
+fn series(task: &Task) -> String {
+    let text = task.completed();
+    text.split(' ').skip(1).filter(|w| !w.starts_with("due:")).collect::<Vec<&str>>().join(" ")
+}
+
+fn today() -> NaiveDate {
+    Local::now().date_naive()
 }
 
 #[cfg(test)]
 mod tests {
-    use mockall::predicate::eq;
+    use mockall::predicate::{eq, function};
 
     use super::*;
     use crate::{MockRepo, Section};
@@ -88,8 +366,29 @@ mod tests {
             .expect_list()
             .returning(|_| Ok(vec!["some_task".to_string(), "some_other_task".to_string()]));
 
-        let mngr = DefaultManager { repo: mock_repo };
-        assert!(mngr.rm("some", Some("section")).is_err());
+        let mngr = DefaultManager::new(mock_repo);
+        let Err(Error::AmbiguousMatch { candidates, .. }) = mngr.rm("some", Some("section")) else {
+            panic!("expected an ambiguous match");
+        };
+        let tasks: Vec<&str> = candidates.iter().map(|c| c.task.as_str()).collect();
+        assert_eq!(tasks, vec!["some_task", "some_other_task"]);
+        assert!(candidates.iter().all(|c| c.section == Section::Custom("section".to_string())));
+    }
+
+    #[test]
+    fn test_rm_selects_task_by_id() {
+        let mut mock_repo = MockRepo::new();
+        mock_repo
+            .expect_list()
+            .returning(|_| Ok(vec!["some_task".to_string(), "some_other_task".to_string()]));
+        mock_repo
+            .expect_delete()
+            .with(eq("some_other_task"), eq(Section::Dump))
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let mngr = DefaultManager::new(mock_repo);
+        assert!(mngr.rm(&task::id("some_other_task"), None).is_ok());
     }
 
     #[test]
@@ -97,8 +396,14 @@ mod tests {
         let mut mock_repo = MockRepo::new();
         mock_repo.expect_list().returning(|_| Ok(vec![]));
 
-        let mngr = DefaultManager { repo: mock_repo };
-        assert!(mngr.rm("some", Some("section")).is_err());
+        let mngr = DefaultManager::new(mock_repo);
+        assert!(matches!(mngr.rm("some", Some("section")), Err(Error::TaskNotFound(_))));
+    }
+
+    #[test]
+    fn test_add_errors_on_empty_task() {
+        let mngr = DefaultManager::new(MockRepo::new());
+        assert!(matches!(mngr.add(" ", None, None), Err(Error::EmptyTask)));
     }
 
     #[test]
@@ -114,7 +419,330 @@ mod tests {
             .times(1)
             .returning(|_, _| Ok(()));
 
-        let mngr = DefaultManager { repo: mock_repo };
+        let mngr = DefaultManager::new(mock_repo);
         assert!(mngr.rm("some", Some("dump")).is_ok());
     }
+
+    #[test]
+    fn test_add_errors_on_long_task() {
+        let mut mock_repo = MockRepo::new();
+        mock_repo.expect_create().times(1).returning(|_, _| Ok(()));
+
+        let mngr = DefaultManager::new(mock_repo).with_max_task_len(9);
+        assert!(mngr.add("some task", None, None).is_ok());
+        assert!(matches!(mngr.add("other task", None, None), Err(Error::TaskTooLong(9))));
+    }
+
+    #[test]
+    fn test_add_resolves_relative_due_dates() {
+        let mut mock_repo = MockRepo::new();
+        mock_repo
+            .expect_create()
+            .with(
+                function(|t: &str| !t.contains("due:tomorrow") && t.contains("due:")),
+                eq(Section::Dump),
+            )
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let mngr = DefaultManager::new(mock_repo);
+        assert!(mngr.add("some task due:tomorrow", None, None).is_ok());
+    }
+
+    #[test]
+    fn test_add_errors_on_invalid_due_date() {
+        let mngr = DefaultManager::new(MockRepo::new());
+        assert!(mngr.add("some task due:someday", None, None).is_err());
+    }
+
+    #[test]
+    fn test_apply_commits_all_ops() {
+        let mut mock_repo = MockRepo::new();
+        mock_repo.expect_begin().times(1).returning(|| Ok(()));
+        mock_repo.expect_create().times(2).returning(|_, _| Ok(()));
+        mock_repo.expect_list().returning(|_| Ok(vec!["some".to_string()]));
+        mock_repo.expect_delete().with(eq("some"), eq(Section::Dump)).returning(|_, _| Ok(()));
+        mock_repo.expect_commit().times(1).returning(|| Ok(()));
+
+        let mngr = DefaultManager::new(mock_repo);
+        let ops = [
+            Op::Add { task: "other".to_string(), section: None },
+            Op::Mv { pattern: "some".to_string(), from: None, to: Some("todo".to_string()) },
+        ];
+        assert!(mngr.apply(&ops, None).is_ok());
+    }
+
+    #[test]
+    fn test_apply_rolls_back_on_error() {
+        let mut mock_repo = MockRepo::new();
+        mock_repo.expect_begin().times(1).returning(|| Ok(()));
+        mock_repo.expect_create().times(1).returning(|_, _| Ok(()));
+        mock_repo.expect_list().returning(|_| Ok(vec![]));
+        mock_repo.expect_rollback().times(1).return_const(());
+        mock_repo.expect_commit().never();
+
+        let mngr = DefaultManager::new(mock_repo);
+        let ops = [
+            Op::Add { task: "other".to_string(), section: None },
+            Op::Rm { pattern: "missing".to_string(), section: None },
+        ];
+        let Err(Error::BatchFailed { index, source }) = mngr.apply(&ops, None) else {
+            panic!("expected the batch to fail");
+        };
+        assert_eq!(index, 1);
+        assert!(matches!(*source, Error::TaskNotFound(_)));
+    }
+
+    #[test]
+    fn test_add_stores_author() {
+        let mut mock_repo = MockRepo::new();
+        mock_repo
+            .expect_create()
+            .with(
+                function(|t: &str| t.starts_with("some task <!-- author:tobi created:20")),
+                eq(Section::Dump),
+            )
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let mngr = DefaultManager::new(mock_repo);
+        let task = mngr.add("some task", None, Some("tobi")).unwrap();
+        assert_eq!((task.text.as_str(), task.author.as_deref()), ("some task", Some("tobi")));
+        assert!(task.created.is_some());
+    }
+
+    #[test]
+    fn test_complete_keeps_metadata() {
+        let line = "some task <!-- author:tobi created:2026-10-19T08:00:00Z -->";
+        let mut mock_repo = MockRepo::new();
+        mock_repo.expect_list().returning(move |_| Ok(vec![line.to_string()]));
+        mock_repo
+            .expect_update()
+            .with(eq(line), eq(format!("[x] {line}")), eq(Section::Dump))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let mngr = DefaultManager::new(mock_repo);
+        // the metadata isn't matched
+        assert!(matches!(mngr.complete("tobi", None), Err(Error::TaskNotFound(_))));
+        assert!(mngr.complete("some task", None).is_ok());
+    }
+
+    #[test]
+    fn test_rm_prefers_exact_match() {
+        let mut mock_repo = MockRepo::new();
+        mock_repo
+            .expect_list()
+            .returning(|_| Ok(vec!["some".to_string(), "some other".to_string()]));
+        mock_repo
+            .expect_delete()
+            .with(eq("some"), eq(Section::Dump))
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let mngr = DefaultManager::new(mock_repo);
+        assert!(mngr.rm("some", None).is_ok());
+    }
+
+    #[test]
+    fn test_search_ranks_matches() {
+        let mut mock_repo = MockRepo::new();
+        mock_repo.expect_list_all().returning(|| {
+            Ok(IndexMap::from([
+                (Section::Dump, vec!["do everything please".to_string(), "other".to_string()]),
+                (Section::Custom("todo".to_string()), vec!["update deps".to_string()]),
+            ]))
+        });
+
+        let mngr = DefaultManager::new(mock_repo);
+        let matches = mngr.search("deps").unwrap();
+        let found: Vec<(Section, &str)> =
+            matches.iter().map(|m| (m.section.clone(), m.task.text.as_str())).collect();
+        assert_eq!(
+            found,
+            vec![
+                (Section::Custom("todo".to_string()), "update deps"),
+                (Section::Dump, "do everything please")
+            ]
+        );
+        assert_eq!(matches[0].positions, vec![7, 8, 9, 10]);
+    }
+
+    #[test]
+    fn test_search_errors_on_empty_query() {
+        let mngr = DefaultManager::new(MockRepo::new());
+        assert!(mngr.search(" ").is_err());
+    }
+
+    #[test]
+    fn test_mv_moves_task() {
+        let mut mock_repo = MockRepo::new();
+        mock_repo.expect_list().returning(|_| Ok(vec!["some task".to_string()]));
+        mock_repo
+            .expect_create()
+            .with(eq("some task"), eq(Section::Custom("todo".to_string())))
+            .times(1)
+            .returning(|_, _| Ok(()));
+        mock_repo
+            .expect_delete()
+            .with(eq("some task"), eq(Section::Dump))
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let mngr = DefaultManager::new(mock_repo);
+        assert!(mngr.mv("some", None, Some("todo")).is_ok());
+    }
+
+    #[test]
+    fn test_complete_checks_off_task() {
+        let mut mock_repo = MockRepo::new();
+        mock_repo.expect_list().returning(|_| Ok(vec!["some task".to_string()]));
+        mock_repo
+            .expect_update()
+            .with(eq("some task"), eq("[x] some task"), eq(Section::Dump))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        mock_repo.expect_create().never();
+
+        let mngr = DefaultManager::new(mock_repo);
+        assert!(mngr.complete("some", None).is_ok());
+    }
+
+    #[test]
+    fn test_complete_adds_next_instance_of_recurring_task() {
+        let mut mock_repo = MockRepo::new();
+        mock_repo.expect_list().returning(|_| Ok(vec!["update deps every:week".to_string()]));
+        mock_repo
+            .expect_update()
+            .with(eq("update deps every:week"), eq("[x] update deps every:week"), eq(Section::Dump))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        mock_repo
+            .expect_create()
+            .with(
+                function(|t: &str| t.starts_with("update deps every:week due:")),
+                eq(Section::Dump),
+            )
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let mngr = DefaultManager::new(mock_repo);
+        assert!(mngr.complete("deps", None).is_ok());
+    }
+
+    #[test]
+    fn test_complete_errors_on_completed_task() {
+        let mut mock_repo = MockRepo::new();
+        mock_repo.expect_list().returning(|_| Ok(vec!["[x] some task".to_string()]));
+
+        let mngr = DefaultManager::new(mock_repo);
+        assert!(mngr.complete("some", None).is_err());
+    }
+
+    #[test]
+    fn test_rollover_adds_missing_instances() {
+        let mut mock_repo = MockRepo::new();
+        mock_repo.expect_list_all().times(1).returning(|| {
+            Ok(IndexMap::from([(
+                Section::Dump,
+                vec![
+                    "[x] update deps due:2026-10-16 every:week".to_string(),
+                    "[x] update deps due:2026-10-09 every:week".to_string(),
+                    "[x] retro notes due:2026-10-16 every:friday".to_string(),
+                    "retro notes due:2026-10-23 every:friday".to_string(),
+                    "[x] not recurring".to_string(),
+                ],
+            )]))
+        });
+        mock_repo
+            .expect_create()
+            .with(function(|t: &str| t.starts_with("update deps due:")), eq(Section::Dump))
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let mngr = DefaultManager::new(mock_repo);
+        let added = mngr.rollover().unwrap();
+        assert_eq!(added.len(), 1);
+        assert_eq!(added[0].recurrence, Some(crate::Recurrence::Weekly));
+    }
+
+    #[test]
+    fn test_new_week_carries_over_unfinished_tasks() {
+        let mut mock_repo = MockRepo::new();
+        mock_repo.expect_list_all().times(1).returning(|| {
+            Ok(IndexMap::from([
+                (
+                    Section::Dump,
+                    vec![
+                        "[x] done task".to_string(),
+                        "open task".to_string(),
+                        "[x] update deps every:week".to_string(),
+                    ],
+                ),
+                (Section::Custom("todo".to_string()), vec!["[ ] open todo".to_string()]),
+            ]))
+        });
+        mock_repo.expect_archive().times(1).returning(|_| Ok(()));
+        mock_repo
+            .expect_create()
+            .with(eq("open task"), eq(Section::Dump))
+            .times(1)
+            .returning(|_, _| Ok(()));
+        mock_repo
+            .expect_create()
+            .with(
+                function(|t: &str| t.starts_with("update deps every:week due:")),
+                eq(Section::Dump),
+            )
+            .times(1)
+            .returning(|_, _| Ok(()));
+        mock_repo
+            .expect_create()
+            .with(eq("[ ] open todo"), eq(Section::Custom("todo".to_string())))
+            .times(1)
+            .returning(|_, _| Ok(()));
+        mock_repo.expect_begin().times(1).returning(|| Ok(()));
+        mock_repo.expect_commit().times(1).returning(|| Ok(()));
+
+        let mngr = DefaultManager::new(mock_repo);
+        assert!(mngr.new_week().is_ok());
+    }
+
+    #[test]
+    fn test_new_week_rolls_back_on_error() {
+        let mut mock_repo = MockRepo::new();
+        mock_repo.expect_begin().times(1).returning(|| Ok(()));
+        mock_repo
+            .expect_list_all()
+            .times(1)
+            .returning(|| Ok(IndexMap::from([(Section::Dump, vec!["open task".to_string()])])));
+        mock_repo.expect_archive().times(1).returning(|_| Ok(()));
+        mock_repo.expect_create().times(1).returning(|_, _| {
+            Err(Error::IO(std::io::Error::new(std::io::ErrorKind::Other, "disk full")))
+        });
+        mock_repo.expect_rollback().times(1).return_const(());
+        mock_repo.expect_commit().never();
+
+        let mngr = DefaultManager::new(mock_repo);
+        assert!(matches!(mngr.new_week(), Err(Error::IO(_))));
+    }
+
+    #[test]
+    fn test_sections_filters_by_tag() {
+        let mut mock_repo = MockRepo::new();
+        mock_repo.expect_list_all().times(1).returning(|| {
+            Ok(IndexMap::from([
+                (Section::Dump, vec!["#infra task".to_string(), "other task".to_string()]),
+                (Section::Custom("todo".to_string()), vec!["nothing tagged".to_string()]),
+            ]))
+        });
+
+        let mngr = DefaultManager::new(mock_repo);
+        let query = Query { tags: vec!["infra".to_string()], ..Query::default() };
+        let sections = mngr.sections(&query).unwrap();
+        assert_eq!(
+            sections,
+            IndexMap::from([(Section::Dump, vec!["#infra task".parse().unwrap()])])
+        );
+    }
 }
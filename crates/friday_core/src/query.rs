@@ -0,0 +1,153 @@
+use core::fmt;
+use std::str;
+
+use chrono::NaiveDate;
+use schemars::JsonSchema;
+use serde_with::{DeserializeFromStr, SerializeDisplay};
+
+use crate::{DueFilter, Error, Task};
+
+/// Query narrows down and orders the tasks returned by a
+/// [`Manager`](crate::Manager). The default query matches all tasks and keeps
+/// them in the order of the file.
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub struct Query {
+    /// only match tasks that have all of these tags
+    pub tags: Vec<String>,
+    /// only match tasks that mention all of these people
+    pub people: Vec<String>,
+    /// only match tasks with a due date selected by the filter
+    pub due: Option<DueFilter>,
+    /// only match tasks assigned to this person
+    pub assignee: Option<String>,
+    /// order of the tasks within each section
+    pub sort: Option<Sort>,
+}
+
+impl Query {
+    pub fn matches(&self, task: &Task, today: NaiveDate) -> bool {
+        self.tags.iter().all(|t| task.has_tag(t))
+            && self.people.iter().all(|p| task.mentions(p))
+            && self.due.map_or(true, |due| due.matches(task.due, today))
+            && self.assignee.as_ref().map_or(true, |a| task.is_assigned_to(a))
+    }
+
+    /// Returns true if the query doesn't filter out any task.
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+            && self.people.is_empty()
+            && self.due.is_none()
+            && self.assignee.is_none()
+    }
+
+    /// Sorts the tasks according to the query.
+    /// Sorting is stable, so tasks that compare equal stay in file order.
+    pub fn sort(&self, tasks: &mut [Task]) {
+        match self.sort {
+            // tasks without a due date go last
+            Some(Sort::Due) => tasks.sort_by_key(|t| (t.due.is_none(), t.due)),
+            // tasks without a priority go last
+            Some(Sort::Priority) => tasks.sort_by_key(|t| (t.priority.is_none(), t.priority)),
+            None => {}
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, DeserializeFromStr, SerializeDisplay, JsonSchema)]
+pub enum Sort {
+    Due,
+    Priority,
+}
+
+impl fmt::Display for Sort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Due => write!(f, "due"),
+            Self::Priority => write!(f, "priority"),
+        }
+    }
+}
+
+impl str::FromStr for Sort {
+    type Err = Error;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "due" => Ok(Self::Due),
+            "priority" => Ok(Self::Priority),
+            any => {
+                Err(Error::InvalidArgument(format!("invalid sort {any}, expected due or priority")))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 19).unwrap()
+    }
+
+    #[test]
+    fn default_matches_everything() {
+        let query = Query::default();
+        assert!(query.is_empty());
+        assert!(query.matches(&"anything".parse().unwrap(), today()));
+    }
+
+    #[test]
+    fn matches_requires_all_tags_and_people() {
+        let task: Task = "#infra #backend with @anna".parse().unwrap();
+        let query = Query {
+            tags: vec!["infra".to_string()],
+            people: vec!["anna".to_string()],
+            ..Query::default()
+        };
+        assert!(query.matches(&task, today()));
+
+        let query =
+            Query { tags: vec!["infra".to_string(), "frontend".to_string()], ..Query::default() };
+        assert!(!query.matches(&task, today()));
+
+        let query = Query { people: vec!["bob".to_string()], ..Query::default() };
+        assert!(!query.matches(&task, today()));
+    }
+
+    #[test]
+    fn matches_due() {
+        let query = Query { due: Some(DueFilter::ThisWeek), ..Query::default() };
+        assert!(query.matches(&Task::parse_at("a due:2026-10-23", today()), today()));
+        assert!(!query.matches(&Task::parse_at("b due:2026-10-30", today()), today()));
+        assert!(!query.matches(&Task::parse_at("c", today()), today()));
+    }
+
+    #[test]
+    fn matches_assignee() {
+        let query = Query { assignee: Some("anna".to_string()), ..Query::default() };
+        assert!(!query.is_empty());
+        assert!(query.matches(&"a assignee:anna".parse().unwrap(), today()));
+        assert!(!query.matches(&"b assignee:bob".parse().unwrap(), today()));
+        assert!(!query.matches(&"c @anna".parse().unwrap(), today()));
+    }
+
+    #[test]
+    fn sort_by_due_keeps_tasks_without_due_last() {
+        let mut tasks: Vec<Task> = ["a", "b due:2026-10-30", "c due:2026-10-23", "d"]
+            .iter()
+            .map(|t| Task::parse_at(t, today()))
+            .collect();
+        Query { sort: Some(Sort::Due), ..Query::default() }.sort(&mut tasks);
+        let texts: Vec<&str> = tasks.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(texts, vec!["c due:2026-10-23", "b due:2026-10-30", "a", "d"]);
+    }
+
+    #[test]
+    fn sort_by_priority_is_stable() {
+        let mut tasks: Vec<Task> =
+            ["a", "b !3", "c (A)", "d !3", "e !1"].iter().map(|t| t.parse().unwrap()).collect();
+        Query { sort: Some(Sort::Priority), ..Query::default() }.sort(&mut tasks);
+        let texts: Vec<&str> = tasks.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(texts, vec!["c (A)", "e !1", "b !3", "d !3", "a"]);
+    }
+}
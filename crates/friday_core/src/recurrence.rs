@@ -0,0 +1,108 @@
+use core::fmt;
+use std::str;
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use schemars::JsonSchema;
+use serde_with::{DeserializeFromStr, SerializeDisplay};
+
+use crate::Error;
+
+const WEEKDAYS: [&str; 7] =
+    ["monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday"];
+
+/// Recurrence of a task, set with an `every:` annotation like `every:week` or
+/// `every:friday`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, DeserializeFromStr, SerializeDisplay, JsonSchema)]
+pub enum Recurrence {
+    Daily,
+    Weekly,
+    /// every week on the given day
+    On(Weekday),
+}
+
+impl Recurrence {
+    /// Returns the date of the first instance after `date`.
+    pub fn after(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Daily => date + Duration::days(1),
+            Self::Weekly => date + Duration::days(7),
+            Self::On(weekday) => {
+                let days_until = (7 + weekday.num_days_from_monday()
+                    - date.weekday().num_days_from_monday())
+                    % 7;
+                date + Duration::days(if days_until == 0 { 7 } else { i64::from(days_until) })
+            }
+        }
+    }
+
+    /// Returns the date of the next instance after `due` that is not in the
+    /// past. Without a due date the first instance after today is returned.
+    pub fn next(&self, due: Option<NaiveDate>, today: NaiveDate) -> NaiveDate {
+        let mut next = self.after(due.unwrap_or(today));
+        while next < today {
+            next = self.after(next);
+        }
+        next
+    }
+}
+
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Daily => write!(f, "day"),
+            Self::Weekly => write!(f, "week"),
+            Self::On(weekday) => write!(f, "{}", WEEKDAYS[weekday.num_days_from_monday() as usize]),
+        }
+    }
+}
+
+impl str::FromStr for Recurrence {
+    type Err = Error;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "day" => Ok(Self::Daily),
+            "week" => Ok(Self::Weekly),
+            any => any.parse().map(Self::On).map_err(|_| {
+                Error::InvalidArgument(format!(
+                    "invalid recurrence {any}, expected day, week or a weekday"
+                ))
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2026-10-19 is a monday
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    macro_rules! test_next {
+        ($name:ident, $rec:expr, $due:expr, $today:expr => $out:expr) => {
+            #[test]
+            fn $name() {
+                let rec: Recurrence = $rec.parse().unwrap();
+                assert_eq!(rec.next($due, $today), $out);
+            }
+        };
+    }
+
+    test_next!(next_weekly, "week", Some(date(23)), date(23) => date(30));
+    test_next!(next_weekly_skips_past_instances, "week", Some(date(2)), date(19) => date(23));
+    test_next!(next_weekly_without_due, "week", None, date(19) => date(26));
+    test_next!(next_daily, "day", Some(date(19)), date(19) => date(20));
+    test_next!(next_weekday, "friday", Some(date(23)), date(23) => date(30));
+    test_next!(next_weekday_without_due, "Friday", None, date(19) => date(23));
+    test_next!(next_weekday_without_due_on_weekday, "friday", None, date(23) => date(30));
+
+    #[test]
+    fn recurrence_roundtrips() {
+        for rec in ["day", "week", "friday"] {
+            assert_eq!(rec.parse::<Recurrence>().unwrap().to_string(), rec);
+        }
+        assert!("fortnight".parse::<Recurrence>().is_err());
+    }
+}
@@ -1,5 +1,12 @@
 use core::fmt;
-use std::{fs, fs::File, io::Write, path::Path, str};
+use std::{
+    fs,
+    fs::File,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    str,
+    sync::Mutex,
+};
 
 use indexmap::IndexMap;
 #[cfg(test)]
@@ -13,10 +20,33 @@ pub trait Repo {
     fn list(&self, section: Section) -> Result<Vec<String>>;
     fn list_all(&self) -> Result<IndexMap<Section, Vec<String>>>;
     fn delete(&self, task: &str, section: Section) -> Result<()>;
+    fn update(&self, task: &str, new: &str, section: Section) -> Result<()>;
+    /// Stores a copy of all tasks under the given name and removes them
+    /// afterwards. Sections and other content are kept.
+    fn archive(&self, name: &str) -> Result<()>;
+    /// Starts a batch of changes. Until it's committed or rolled back the
+    /// changes are only visible to this repo.
+    fn begin(&self) -> Result<()>;
+    /// Stores all changes of the batch at once.
+    fn commit(&self) -> Result<()>;
+    /// Discards all changes of the batch.
+    fn rollback(&self);
+    /// Returns an error if the tasks can't be read or written.
+    fn check(&self) -> Result<()>;
 }
 
 pub struct FileBacked<T: AsRef<Path>> {
     file: T,
+    /// the changes of the current batch
+    staged: Mutex<Option<Staged>>,
+}
+
+/// Staged are the changes of a batch, they're written on commit.
+struct Staged {
+    /// the content of the file
+    content: String,
+    /// the paths and contents of the archives to create
+    archives: Vec<(PathBuf, String)>,
 }
 
 struct Line {
@@ -78,11 +108,33 @@ impl<T: AsRef<Path>> FileBacked<T> {
 - this where stuff lands by default"
             )?;
         }
-        Ok(FileBacked { file: path })
+        Ok(FileBacked { file: path, staged: Mutex::new(None) })
+    }
+
+    // span is the span of a repo operation, every one enters it so that the
+    // file is part of all of them.
+    fn span(&self, operation: &'static str) -> tracing::Span {
+        tracing::debug_span!("repo", operation, file = %self.file.as_ref().display())
+    }
+
+    fn read(&self) -> Result<String> {
+        if let Some(staged) = self.staged.lock().unwrap().as_ref() {
+            return Ok(staged.content.clone());
+        }
+        Ok(fs::read_to_string(&self.file)?)
+    }
+
+    fn write(&self, content: String) -> Result<()> {
+        if let Some(staged) = self.staged.lock().unwrap().as_mut() {
+            staged.content = content;
+            return Ok(());
+        }
+        fs::write(&self.file, content)?;
+        Ok(())
     }
 
     fn lines(&self) -> Result<Vec<Line>> {
-        let file_content = fs::read_to_string(&self.file)?;
+        let file_content = self.read()?;
         let mut current_section = Section::default();
         let mut lines = Vec::new();
         // the use of split instead of lines() is intended to keep the
@@ -101,13 +153,37 @@ impl<T: AsRef<Path>> FileBacked<T> {
     fn dump_lines(&self, lines: &[Line]) -> Result<()> {
         let content =
             lines.iter().map(|l| l.content.to_string()).collect::<Vec<String>>().join("\n");
-        fs::write(&self.file, content)?;
-        Ok(())
+        self.write(content)
     }
 }
 
+// create_archive writes an archive, existing ones are never overwritten
+fn create_archive(archive: &Path, content: &str) -> Result<()> {
+    let mut file =
+        File::options().write(true).create_new(true).open(archive).map_err(|e| match e {
+            e if e.kind() == io::ErrorKind::AlreadyExists => archive_exists(archive),
+            e => Error::from(e),
+        })?;
+    file.write_all(content.as_bytes())?;
+    Ok(())
+}
+
+fn archive_exists(archive: &Path) -> Error {
+    let name = archive.file_stem().unwrap_or_default().to_string_lossy();
+    Error::InvalidArgument(format!("archive {name} already exists"))
+}
+
+fn find_task(lines: &[Line], task: &str, section: &Section) -> Option<usize> {
+    lines.iter().position(|line| {
+        matches!(line.content, LineContent::Task(_))
+            && &line.section == section
+            && line.content.stripped() == task
+    })
+}
+
 impl<T: AsRef<Path>> Repo for FileBacked<T> {
     fn create(&self, task: &str, section: Section) -> Result<()> {
+        let _span = self.span("create").entered();
         let mut lines = self.lines()?;
 
         let mut last_line_in_section = None;
@@ -157,36 +233,102 @@ impl<T: AsRef<Path>> Repo for FileBacked<T> {
     }
 
     fn delete(&self, task: &str, section: Section) -> Result<()> {
+        let _span = self.span("delete").entered();
         let mut lines = self.lines()?;
-        let mut remove_index = None;
-        for (i, line) in lines.iter().enumerate() {
-            if !matches!(line.content, LineContent::Task(_)) || line.section != section {
-                continue;
-            }
+        let Some(remove_index) = find_task(&lines, task, &section) else {
+            return Ok(());
+        };
 
-            if line.content.stripped() == task {
-                remove_index = Some(i);
-                break;
-            }
+        lines.remove(remove_index);
+        self.dump_lines(&lines)
+    }
+
+    fn update(&self, task: &str, new: &str, section: Section) -> Result<()> {
+        let _span = self.span("update").entered();
+        let mut lines = self.lines()?;
+        let i = find_task(&lines, task, &section)
+            .ok_or_else(|| Error::TaskNotFound(task.to_string()))?;
+
+        // keep the indentation and list marker of the original line
+        let line = lines[i].content.to_string();
+        let prefix_len = line.len() - line.trim_start().len() + 1;
+        lines[i].content = LineContent::Task(format!("{} {new}", &line[..prefix_len]));
+        self.dump_lines(&lines)
+    }
+
+    // archives are stored in a directory next to the file, e.g.
+    // `friday.md` is archived to `friday-archive/<name>.md`.
+    fn archive(&self, name: &str) -> Result<()> {
+        let _span = self.span("archive").entered();
+        let path = self.file.as_ref();
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let dir = path.with_file_name(format!("{stem}-archive"));
+        fs::create_dir_all(&dir)?;
+
+        let mut archive = dir.join(name);
+        if let Some(extension) = path.extension() {
+            archive.set_extension(extension);
         }
-        if remove_index.is_none() {
-            return Ok(());
+        let content = self.read()?;
+        match self.staged.lock().unwrap().as_mut() {
+            // archives of a batch are only created on commit, so that
+            // rolled back batches don't leave them behind
+            Some(_) if archive.exists() => return Err(archive_exists(&archive)),
+            Some(staged) => staged.archives.push((archive, content)),
+            None => create_archive(&archive, &content)?,
         }
 
-        lines.remove(remove_index.unwrap());
+        let mut lines = self.lines()?;
+        lines.retain(|l| !matches!(l.content, LineContent::Task(_)));
         self.dump_lines(&lines)
     }
 
+    fn begin(&self) -> Result<()> {
+        let _span = self.span("begin").entered();
+        let content = fs::read_to_string(&self.file)?;
+        let mut staged = self.staged.lock().unwrap();
+        if staged.is_some() {
+            return Err(Error::InvalidCommand("a batch is already in progress".to_string()));
+        }
+        *staged = Some(Staged { content, archives: vec![] });
+        Ok(())
+    }
+
+    fn commit(&self) -> Result<()> {
+        let _span = self.span("commit").entered();
+        let Some(staged) = self.staged.lock().unwrap().take() else {
+            return Err(Error::InvalidCommand("no batch in progress".to_string()));
+        };
+        for (archive, content) in &staged.archives {
+            create_archive(archive, content)?;
+        }
+        fs::write(&self.file, staged.content)?;
+        Ok(())
+    }
+
+    fn rollback(&self) {
+        let _span = self.span("rollback").entered();
+        self.staged.lock().unwrap().take();
+    }
+
+    // the file is opened for appending to check the permissions without
+    // changing it.
+    fn check(&self) -> Result<()> {
+        let _span = self.span("check").entered();
+        File::options().read(true).append(true).open(&self.file)?;
+        Ok(())
+    }
+
     fn list(&self, section: Section) -> Result<Vec<String>> {
+        let _span = self.span("list").entered();
         let sections = self.list_all()?;
-        let tasks = sections
-            .get(&section)
-            .ok_or_else(|| Error::InvalidArgument(format!("section {section} not found")))?
-            .clone();
+        let tasks =
+            sections.get(&section).ok_or_else(|| Error::SectionNotFound(section.clone()))?.clone();
         Ok(tasks)
     }
 
     fn list_all(&self) -> Result<IndexMap<Section, Vec<String>>> {
+        let _span = self.span("list_all").entered();
         let mut sections_to_tasks: IndexMap<Section, Vec<String>> = IndexMap::new();
         let lines = self.lines()?;
         let task_lines = lines.iter().filter(|l| matches!(l.content, LineContent::Task(_)));
@@ -219,6 +361,15 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn check_fails_for_missing_file() -> Result<(), Box<dyn Error>> {
+        let (file_repo, _tmp_dir) = setup("")?;
+        assert!(file_repo.check().is_ok());
+        fs::remove_file(&file_repo.file)?;
+        assert!(file_repo.check().is_err());
+        Ok(())
+    }
+
     // the returned temp_dir is only returned to keep the reference and not destroy
     // it before the function tests are done.
     fn setup(content: &str) -> Result<(FileBacked<PathBuf>, TempDir), Box<dyn Error>> {
@@ -295,7 +446,10 @@ mod tests {
     #[test]
     fn list_returns_error_on_not_found() {
         let (file_repo, _tmp_dir) = setup("").unwrap();
-        assert!(file_repo.list(Section::Custom("non-existent".to_string())).is_err());
+        assert!(matches!(
+            file_repo.list(Section::Custom("non-existent".to_string())),
+            Err(crate::Error::SectionNotFound(_))
+        ));
     }
 
     #[test]
@@ -350,6 +504,89 @@ mod tests {
         };
     }
 
+    #[test]
+    fn update_keeps_indentation_and_marker() -> Result<(), Box<dyn Error>> {
+        let (file_repo, _tmp_dir) = setup("## Dump\n- something\n  - something else\n")?;
+        file_repo.update("something else", "[x] something else", Section::Dump)?;
+        let content = fs::read_to_string(&file_repo.file)?;
+        assert_eq!(content, "## Dump\n- something\n  - [x] something else\n");
+        Ok(())
+    }
+
+    #[test]
+    fn archive_moves_tasks_to_archive() -> Result<(), Box<dyn Error>> {
+        let initial_content = "# Heading\n## todo\n- something\n\n## Dump\n- something else\n";
+        let (file_repo, tmp_dir) = setup(initial_content)?;
+        file_repo.archive("2026-10-23")?;
+
+        let archive = fs::read_to_string(tmp_dir.path().join("testing-archive/2026-10-23"))?;
+        assert_eq!(archive, initial_content);
+        let content = fs::read_to_string(&file_repo.file)?;
+        assert_eq!(content, "# Heading\n## todo\n\n## Dump\n");
+        Ok(())
+    }
+
+    #[test]
+    fn archive_returns_error_if_archive_exists() -> Result<(), Box<dyn Error>> {
+        let (file_repo, _tmp_dir) = setup("## Dump\n- something\n")?;
+        file_repo.archive("2026-10-23")?;
+        assert!(file_repo.archive("2026-10-23").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn batch_writes_on_commit() -> Result<(), Box<dyn Error>> {
+        let (file_repo, _tmp_dir) = setup("## Dump\n- something\n")?;
+        file_repo.begin()?;
+        file_repo.create("something else", Section::Dump)?;
+        file_repo.delete("something", Section::Dump)?;
+        assert_eq!(file_repo.list(Section::Dump)?, vec!["something else"]);
+        assert_eq!(fs::read_to_string(&file_repo.file)?, "## Dump\n- something\n");
+
+        file_repo.commit()?;
+        assert_eq!(fs::read_to_string(&file_repo.file)?, "## Dump\n- something else\n");
+        assert!(file_repo.commit().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn batch_discards_on_rollback() -> Result<(), Box<dyn Error>> {
+        let (file_repo, _tmp_dir) = setup("## Dump\n- something\n")?;
+        file_repo.begin()?;
+        assert!(file_repo.begin().is_err());
+        file_repo.delete("something", Section::Dump)?;
+        file_repo.rollback();
+
+        assert_eq!(file_repo.list(Section::Dump)?, vec!["something"]);
+        assert_eq!(fs::read_to_string(&file_repo.file)?, "## Dump\n- something\n");
+        Ok(())
+    }
+
+    #[test]
+    fn batch_creates_archives_on_commit() -> Result<(), Box<dyn Error>> {
+        let (file_repo, tmp_dir) = setup("## Dump\n- something\n")?;
+        let archive = tmp_dir.path().join("testing-archive/2026-10-23");
+        file_repo.begin()?;
+        file_repo.archive("2026-10-23")?;
+        assert!(!archive.exists());
+        file_repo.rollback();
+        assert!(!archive.exists());
+
+        file_repo.begin()?;
+        file_repo.archive("2026-10-23")?;
+        file_repo.create("something else", Section::Dump)?;
+        file_repo.commit()?;
+        assert_eq!(fs::read_to_string(&archive)?, "## Dump\n- something\n");
+        assert_eq!(fs::read_to_string(&file_repo.file)?, "## Dump\n- something else\n");
+        Ok(())
+    }
+
+    #[test]
+    fn update_returns_error_on_not_found() {
+        let (file_repo, _tmp_dir) = setup("## Dump\n- something\n").unwrap();
+        assert!(file_repo.update("something else", "new", Section::Dump).is_err());
+    }
+
     test_create!(
         create_adds_to_existing_section,
         RepoTest {
@@ -0,0 +1,110 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{Section, Task};
+
+// bonuses for matched characters, everything else just counts one point.
+const CONSECUTIVE_BONUS: i64 = 4;
+const WORD_START_BONUS: i64 = 6;
+
+/// A task found by [`Manager::search`](crate::Manager::search).
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Match {
+    /// the section the task is in
+    pub section: Section,
+    pub task: Task,
+    /// how well the task matches, higher is better
+    pub score: i64,
+    /// the char indices of the matched characters in the task text
+    pub positions: Vec<usize>,
+}
+
+/// Matches the query fuzzily against the text, i.e. all characters of the
+/// query need to appear in the text in the same order but not necessarily
+/// next to each other. Case and whitespace in the query are ignored.
+///
+/// Returns the score and the char indices of the matched characters.
+/// Matches at the start of words and consecutive characters score higher,
+/// gaps between matched characters lower the score.
+pub fn fuzzy_match(text: &str, query: &str) -> Option<(i64, Vec<usize>)> {
+    let text: Vec<char> = text.chars().collect();
+    let query: Vec<char> = query.chars().filter(|c| !c.is_whitespace()).collect();
+    if query.is_empty() {
+        return None;
+    }
+
+    // best[i][j] holds the best score for matching query[..=i] with query[i]
+    // at text[j] and the position of query[i - 1] in that match. The lines in
+    // a friday file are short, so the quadratic lookback is fine.
+    let mut best: Vec<Vec<Option<(i64, usize)>>> = vec![vec![None; text.len()]; query.len()];
+    for (i, &q) in query.iter().enumerate() {
+        for j in (i..text.len()).filter(|&j| eq(text[j], q)) {
+            let bonus = if j == 0 || !text[j - 1].is_alphanumeric() { WORD_START_BONUS } else { 0 };
+            if i == 0 {
+                best[i][j] = Some((1 + bonus, 0));
+                continue;
+            }
+            // iterating backwards lets the first of equally good matches win,
+            // which keeps the highlighting predictable
+            best[i][j] = (0..j)
+                .rev()
+                .filter_map(|k| {
+                    let (prev, _) = best[i - 1][k]?;
+                    let gap = i64::try_from(j - k - 1).ok()?;
+                    let consecutive = if gap == 0 { CONSECUTIVE_BONUS } else { 0 };
+                    Some((prev + 1 + bonus + consecutive - gap, k))
+                })
+                .max_by_key(|(score, _)| *score);
+        }
+    }
+
+    let last = query.len() - 1;
+    let (mut pos, (score, _)) = best[last]
+        .iter()
+        .enumerate()
+        .rev()
+        .filter_map(|(j, m)| Some((j, (*m)?)))
+        .max_by_key(|(_, (score, _))| *score)?;
+    let mut positions = vec![pos];
+    for i in (1..=last).rev() {
+        // every entry that is set has its predecessor set as well
+        pos = best[i][pos]?.1;
+        positions.push(pos);
+    }
+    positions.reverse();
+    Some((score, positions))
+}
+
+fn eq(a: char, b: char) -> bool {
+    a.to_lowercase().eq(b.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_fuzzy_match {
+        ($name:ident, $text:expr, $query:expr => $positions:expr) => {
+            #[test]
+            fn $name() {
+                let positions: Option<Vec<usize>> = $positions;
+                assert_eq!(fuzzy_match($text, $query).map(|(_, p)| p), positions);
+            }
+        };
+    }
+
+    test_fuzzy_match!(match_substring, "update deps", "deps" => Some(vec![7, 8, 9, 10]));
+    test_fuzzy_match!(match_scattered, "update deps", "upd" => Some(vec![0, 1, 2]));
+    test_fuzzy_match!(match_ignores_case, "Update Deps", "ud" => Some(vec![0, 7]));
+    test_fuzzy_match!(match_ignores_whitespace, "update deps", "up deps" => Some(vec![0, 1, 7, 8, 9, 10]));
+    test_fuzzy_match!(match_prefers_word_start, "redeploy deps", "dep" => Some(vec![9, 10, 11]));
+    test_fuzzy_match!(match_wrong_order, "update deps", "spu" => None);
+    test_fuzzy_match!(match_empty_query, "update deps", " " => None);
+
+    #[test]
+    fn fuzzy_match_ranks_closer_matches_higher() {
+        let (close, _) = fuzzy_match("update deps", "deps").unwrap();
+        let (scattered, _) = fuzzy_match("do everything please, sir", "deps").unwrap();
+        assert!(close > scattered);
+    }
+}
@@ -0,0 +1,395 @@
+use core::fmt;
+use std::str;
+
+use chrono::{DateTime, Local, NaiveDate, SecondsFormat, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{due, Error, Recurrence};
+
+const DONE_MARKER: &str = "[x] ";
+const OPEN_MARKER: &str = "[ ] ";
+const META_START: &str = "<!--";
+const META_END: &str = "-->";
+
+/// Task is a single item in a [`Section`](crate::Section).
+/// Besides the raw text it holds the annotations that were parsed out of it.
+/// The author and creation time are stored in a trailing comment like
+/// `<!-- author:tobi created:2026-10-19T08:00:00Z -->` that isn't part of the
+/// text.
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Task {
+    /// a short hash of the text, it changes whenever the text changes
+    pub id: String,
+    /// the task as written in the file without the leading list marker and
+    /// the metadata comment
+    pub text: String,
+    /// all `#tag` tokens without the leading `#`
+    pub tags: Vec<String>,
+    /// all `@person` tokens without the leading `@`
+    pub people: Vec<String>,
+    /// the date from a `due:` annotation, see [`parse_date`](crate::parse_date)
+    pub due: Option<NaiveDate>,
+    /// the priority from a `!1`..`!3` or `(A)`..`(C)` marker
+    pub priority: Option<Priority>,
+    /// the recurrence from an `every:` annotation
+    pub recurrence: Option<Recurrence>,
+    /// whether the task is checked off with `[x]`
+    pub done: bool,
+    /// the person from an `assignee:` annotation
+    pub assignee: Option<String>,
+    /// who added the task
+    pub author: Option<String>,
+    /// when the task was added
+    pub created: Option<DateTime<Utc>>,
+}
+
+/// Priority of a task. `High` sorts first.
+#[derive(
+    PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    High,
+    Medium,
+    Low,
+}
+
+impl str::FromStr for Priority {
+    type Err = Error;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "!1" | "(A)" => Ok(Self::High),
+            "!2" | "(B)" => Ok(Self::Medium),
+            "!3" | "(C)" => Ok(Self::Low),
+            any => Err(Error::InvalidArgument(format!("invalid priority {any}"))),
+        }
+    }
+}
+
+impl Task {
+    /// Parses a task, resolving relative annotations against `today`.
+    pub fn parse_at(s: &str, today: NaiveDate) -> Self {
+        let (s, meta) = split_meta(s);
+        let mut tags = Vec::new();
+        let mut people = Vec::new();
+        let mut due = None;
+        let mut priority = None;
+        let mut recurrence = None;
+        let mut assignee = None;
+        for word in s.split_whitespace() {
+            if let Some(tag) = token(word, '#') {
+                tags.push(tag);
+            } else if let Some(person) = token(word, '@') {
+                people.push(person);
+            } else if let Some(value) = word.strip_prefix("assignee:") {
+                assignee = token(&format!("@{}", value.trim_start_matches('@')), '@');
+            } else if let Some(value) = word.strip_prefix("due:") {
+                due = due::parse_date(value, today);
+            } else if let Some(value) = word.strip_prefix("every:") {
+                recurrence = value.parse().ok();
+            } else if let Ok(p) = word.parse() {
+                // the first marker wins, like for todo.txt
+                priority = priority.or(Some(p));
+            }
+        }
+        let done =
+            s.get(..DONE_MARKER.len()).map_or(false, |m| m.eq_ignore_ascii_case(DONE_MARKER));
+
+        let mut author = None;
+        let mut created = None;
+        for word in meta.unwrap_or_default().split_whitespace() {
+            if let Some(value) = word.strip_prefix("author:") {
+                author = Some(value.to_string());
+            } else if let Some(value) = word.strip_prefix("created:") {
+                created = DateTime::parse_from_rfc3339(value).ok().map(|d| d.with_timezone(&Utc));
+            }
+        }
+        Self {
+            id: id(s),
+            text: s.to_string(),
+            tags,
+            people,
+            due,
+            priority,
+            recurrence,
+            done,
+            assignee,
+            author,
+            created,
+        }
+    }
+
+    /// Returns the task as it's stored in the file, with the metadata comment
+    /// if there is any metadata.
+    pub fn line(&self) -> String {
+        self.with_meta(&self.text)
+    }
+
+    // with_meta appends the metadata of the task to another text, e.g. to keep
+    // it when the task is checked off.
+    pub(crate) fn with_meta(&self, text: &str) -> String {
+        let mut meta = Vec::new();
+        if let Some(author) = &self.author {
+            meta.push(format!("author:{author}"));
+        }
+        if let Some(created) = &self.created {
+            meta.push(format!("created:{}", created.to_rfc3339_opts(SecondsFormat::Secs, true)));
+        }
+        if meta.is_empty() {
+            return text.to_string();
+        }
+        format!("{text} {META_START} {} {META_END}", meta.join(" "))
+    }
+
+    pub fn is_assigned_to(&self, person: &str) -> bool {
+        let person = person.trim_start_matches('@');
+        self.assignee.as_ref().map_or(false, |a| a.eq_ignore_ascii_case(person))
+    }
+
+    /// Returns the text of the task checked off with `[x]`.
+    pub fn completed(&self) -> String {
+        if self.done {
+            return self.text.clone();
+        }
+        let text = self.text.strip_prefix(OPEN_MARKER).unwrap_or(&self.text);
+        format!("{DONE_MARKER}{text}")
+    }
+
+    /// Returns the text of the next instance of a recurring task.
+    /// The instance is not checked off and due on the next recurrence after
+    /// the current due date.
+    pub fn next_instance(&self, today: NaiveDate) -> Option<String> {
+        let recurrence = self.recurrence?;
+        let next_due = format!("due:{}", recurrence.next(self.due, today).format("%Y-%m-%d"));
+
+        let text = if self.done { &self.text[DONE_MARKER.len()..] } else { &self.text };
+        let mut replaced = false;
+        let mut words: Vec<&str> = text
+            .split(' ')
+            .map(|w| {
+                if w.starts_with("due:") {
+                    replaced = true;
+                    return next_due.as_str();
+                }
+                w
+            })
+            .collect();
+        if !replaced {
+            words.push(&next_due);
+        }
+        Some(words.join(" "))
+    }
+
+    pub fn is_overdue(&self, today: NaiveDate) -> bool {
+        self.due.map_or(false, |due| due < today)
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        let tag = tag.trim_start_matches('#');
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    pub fn mentions(&self, person: &str) -> bool {
+        let person = person.trim_start_matches('@');
+        self.people.iter().any(|p| p.eq_ignore_ascii_case(person))
+    }
+}
+
+impl fmt::Display for Task {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+impl str::FromStr for Task {
+    type Err = std::convert::Infallible;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(Self::parse_at(s, Local::now().date_naive()))
+    }
+}
+
+// id hashes the text with 32 bit FNV-1a, since the hasher of the std lib is not
+// guaranteed to be stable between releases. The metadata isn't part of the
+// hash, so the ids of existing tasks don't change when it's added.
+pub(crate) fn id(text: &str) -> String {
+    let hash = split_meta(text)
+        .0
+        .bytes()
+        .fold(0x811c_9dc5_u32, |hash, b| (hash ^ u32::from(b)).wrapping_mul(0x0100_0193));
+    format!("{hash:08x}")
+}
+
+// split_meta splits the trailing metadata comment off a task and returns the
+// text and the content of the comment. Other comments are part of the text.
+pub(crate) fn split_meta(s: &str) -> (&str, Option<&str>) {
+    let Some(rest) = s.trim_end().strip_suffix(META_END) else {
+        return (s, None);
+    };
+    let Some(start) = rest.rfind(META_START) else {
+        return (s, None);
+    };
+    let meta = rest[start + META_START.len()..].trim();
+    let is_meta = !meta.is_empty()
+        && meta.split_whitespace().all(|w| w.starts_with("author:") || w.starts_with("created:"));
+    if !is_meta {
+        return (s, None);
+    }
+    (s[..start].trim_end(), Some(meta))
+}
+
+// token returns the value of a word like `#infra` or `@tobi` if it starts with
+// the given marker. Trailing punctuation is not considered part of the token
+// to support e.g. "ask @tobi, he knows".
+fn token(word: &str, marker: char) -> Option<String> {
+    let value = word.strip_prefix(marker)?;
+    let value = value.trim_end_matches(|c: char| c.is_ascii_punctuation() && c != '-' && c != '_');
+    if value.is_empty() || !value.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+        return None;
+    }
+    Some(value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_parse {
+        ($name:ident, $in:expr, tags: [$($tag:expr),*], people: [$($person:expr),*]) => {
+            #[test]
+            fn $name() {
+                let task: Task = $in.parse().unwrap();
+                assert_eq!(task.text, $in);
+                assert_eq!(task.tags, vec![$($tag.to_string()),*] as Vec<String>);
+                assert_eq!(task.people, vec![$($person.to_string()),*] as Vec<String>);
+            }
+        };
+    }
+
+    test_parse!(parse_plain, "just some task", tags: [], people: []);
+    test_parse!(parse_tag, "fix the #infra pipeline", tags: ["infra"], people: []);
+    test_parse!(parse_person, "ask @tobi about it", tags: [], people: ["tobi"]);
+    test_parse!(
+        parse_multiple,
+        "#backend sync with @anna and @bob on #api-design",
+        tags: ["backend", "api-design"],
+        people: ["anna", "bob"]
+    );
+    test_parse!(parse_trailing_punctuation, "ask @tobi, see #infra.", tags: ["infra"], people: ["tobi"]);
+    test_parse!(parse_ignores_inline_markers, "mail me at a@b.com, issue#12 and #", tags: [], people: []);
+
+    #[test]
+    fn parse_due() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+        let task = Task::parse_at("update deps due:2026-10-23", today);
+        assert_eq!(task.due, NaiveDate::from_ymd_opt(2026, 10, 23));
+        assert!(!task.is_overdue(today));
+        assert!(task.is_overdue(NaiveDate::from_ymd_opt(2026, 10, 24).unwrap()));
+
+        let task = Task::parse_at("update deps due:next-friday", today);
+        assert_eq!(task.due, NaiveDate::from_ymd_opt(2026, 10, 30));
+
+        let task = Task::parse_at("update deps due:someday", today);
+        assert_eq!(task.due, None);
+    }
+
+    macro_rules! test_priority {
+        ($name:ident, $in:expr => $out:expr) => {
+            #[test]
+            fn $name() {
+                let task: Task = $in.parse().unwrap();
+                assert_eq!(task.priority, $out);
+            }
+        };
+    }
+
+    test_priority!(priority_none, "some task" => None);
+    test_priority!(priority_bang, "!1 some task" => Some(Priority::High));
+    test_priority!(priority_bang_trailing, "some task !3" => Some(Priority::Low));
+    test_priority!(priority_letter, "(B) some task" => Some(Priority::Medium));
+    test_priority!(priority_first_wins, "(A) some task !3" => Some(Priority::High));
+    test_priority!(priority_ignores_unknown, "(D) some task !4 !!1" => None);
+
+    #[test]
+    fn parse_done() {
+        assert!("[x] some task".parse::<Task>().unwrap().done);
+        assert!("[X] some task".parse::<Task>().unwrap().done);
+        assert!(!"[ ] some task".parse::<Task>().unwrap().done);
+        assert!(!"some task [x]".parse::<Task>().unwrap().done);
+    }
+
+    #[test]
+    fn completed_checks_off_task() {
+        assert_eq!("some task".parse::<Task>().unwrap().completed(), "[x] some task");
+        assert_eq!("[ ] some task".parse::<Task>().unwrap().completed(), "[x] some task");
+        assert_eq!("[x] some task".parse::<Task>().unwrap().completed(), "[x] some task");
+    }
+
+    #[test]
+    fn next_instance_moves_due_date() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 23).unwrap();
+        let task = Task::parse_at("[x] update deps due:2026-10-23 every:week #infra", today);
+        assert_eq!(task.recurrence, Some(Recurrence::Weekly));
+        assert_eq!(
+            task.next_instance(today),
+            Some("update deps due:2026-10-30 every:week #infra".to_string())
+        );
+
+        let task = Task::parse_at("retro notes every:friday", today);
+        assert_eq!(
+            task.next_instance(today),
+            Some("retro notes every:friday due:2026-10-30".to_string())
+        );
+
+        let task = Task::parse_at("not recurring", today);
+        assert_eq!(task.next_instance(today), None);
+    }
+
+    #[test]
+    fn id_is_stable() {
+        assert_eq!("a".parse::<Task>().unwrap().id, "e40c292c");
+        assert_eq!("some task".parse::<Task>().unwrap().id, id("some task"));
+        assert_ne!("some task".parse::<Task>().unwrap().id, id("[x] some task"));
+    }
+
+    #[test]
+    fn parse_meta() {
+        let task: Task =
+            "update deps #infra <!-- author:tobi created:2026-10-19T08:00:00Z -->".parse().unwrap();
+        assert_eq!(task.text, "update deps #infra");
+        assert_eq!(task.id, id("update deps #infra"));
+        assert_eq!(task.author.as_deref(), Some("tobi"));
+        assert_eq!(task.created.map(|c| c.to_rfc3339()), Some("2026-10-19T08:00:00+00:00".into()));
+        assert_eq!(
+            task.line(),
+            "update deps #infra <!-- author:tobi created:2026-10-19T08:00:00Z -->"
+        );
+
+        let task: Task = "update deps <!-- some note -->".parse().unwrap();
+        assert_eq!((task.text.as_str(), task.author.as_deref()), ("update deps <!-- some note -->", None));
+        assert_eq!(task.line(), task.text);
+
+        let task: Task = "compare a<!--b".parse().unwrap();
+        assert_eq!(task.text, "compare a<!--b");
+    }
+
+    #[test]
+    fn parse_assignee() {
+        let task: Task = "review PR assignee:@anna".parse().unwrap();
+        assert_eq!(task.assignee.as_deref(), Some("anna"));
+        assert!(task.is_assigned_to("Anna"));
+        assert!(task.people.is_empty());
+
+        let task: Task = "review PR assignee:tobi".parse().unwrap();
+        assert!(task.is_assigned_to("@tobi"));
+        assert!(!"review PR @tobi".parse::<Task>().unwrap().is_assigned_to("tobi"));
+    }
+
+    #[test]
+    fn has_tag_ignores_case_and_marker() {
+        let task: Task = "some #Infra task".parse().unwrap();
+        assert!(task.has_tag("infra"));
+        assert!(task.has_tag("#INFRA"));
+        assert!(!task.has_tag("backend"));
+    }
+}
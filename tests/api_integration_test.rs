@@ -0,0 +1,352 @@
+mod common;
+
+use std::{
+    error::Error,
+    fs,
+    io::{Read, Write},
+    net::TcpStream,
+    thread,
+    time::Duration,
+};
+
+use assert_cmd::Command;
+use common::Server;
+use friday_client::{
+    Batch,
+    CreateTask,
+    ListResponse,
+    Problem,
+    NEXT_CURSOR_HEADER,
+    TOTAL_COUNT_HEADER,
+};
+use friday_core::{Op, Task};
+use serde_json::{json, Value};
+use tempfile::TempDir;
+
+#[test]
+fn it_creates_tasks() -> Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new()?;
+    let file_path = tmp_dir.path().join("api.md");
+    fs::write(&file_path, "## dump\n\n- existing task\n")?;
+    let server = Server::start(&file_path)?;
+
+    let res = ureq::post(&format!("{}/tasks", server.url))
+        .send_json(CreateTask { task: "new task #infra".to_string() })?;
+    assert_eq!(res.status(), 201);
+    let location = res.header("Location").unwrap().to_string();
+    let created: Task = res.into_json()?;
+    assert_eq!(created.text, "new task #infra");
+    assert_eq!(location, format!("/tasks/dump/{}", created.id));
+    assert!(fs::read_to_string(&file_path)?.contains("- new task #infra <!-- created:"));
+    assert!(created.created.is_some());
+
+    let task: Task = ureq::get(&format!("{}{location}", server.url)).call()?.into_json()?;
+    assert_eq!(task, created);
+
+    let res = ureq::post(&format!("{}/tasks", server.url))
+        .send_json(CreateTask { task: " ".to_string() });
+    assert!(matches!(res, Err(ureq::Error::Status(400, _))));
+    let res = ureq::get(&format!("{}/tasks/dump/00000000", server.url)).call();
+    assert!(matches!(res, Err(ureq::Error::Status(404, _))));
+    Ok(())
+}
+
+#[test]
+fn it_responds_with_problems() -> Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new()?;
+    let file_path = tmp_dir.path().join("api.md");
+    fs::write(&file_path, "## dump\n\n- update deps\n- review deps PR\n")?;
+    let server = Server::start(&file_path)?;
+
+    let res = ureq::delete(&format!("{}/tasks/dump", server.url)).query("pattern", "deps").call();
+    let Err(ureq::Error::Status(409, res)) = res else {
+        panic!("expected a conflict");
+    };
+    assert_eq!(res.content_type(), "application/problem+json");
+    let problem: Problem = res.into_json()?;
+    assert_eq!(problem.kind, "urn:friday:problem:ambiguous-match");
+    assert_eq!(problem.status, 409);
+    let tasks: Vec<&str> = problem.candidates.iter().map(|c| c.task.as_str()).collect();
+    assert_eq!(tasks, vec!["update deps", "review deps PR"]);
+
+    // the id of a candidate selects it
+    let url = format!("{}/tasks/dump/{}", server.url, problem.candidates[0].id);
+    ureq::delete(&url).call()?;
+    assert_eq!(fs::read_to_string(&file_path)?, "## dump\n\n- review deps PR\n");
+
+    let res = ureq::get(&format!("{}/tasks/todo", server.url)).call();
+    let Err(ureq::Error::Status(404, res)) = res else {
+        panic!("expected not found");
+    };
+    let problem: Problem = res.into_json()?;
+    assert_eq!(problem.kind, "urn:friday:problem:section-not-found");
+    assert_eq!(problem.detail, "section todo not found");
+    Ok(())
+}
+
+#[test]
+fn it_applies_batches() -> Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new()?;
+    let file_path = tmp_dir.path().join("api.md");
+    fs::write(&file_path, "## dump\n\n- update deps\n")?;
+    let server = Server::start(&file_path)?;
+    let url = format!("{}/tasks:batch", server.url);
+
+    let ops = vec![
+        Op::Add { task: "new task".to_string(), section: None },
+        Op::Rm { pattern: "deps".to_string(), section: None },
+    ];
+    ureq::post(&url).send_json(Batch { ops })?;
+    let content = fs::read_to_string(&file_path)?;
+    assert!(content.starts_with("## dump\n\n- new task <!-- created:"), "got '{content}'");
+
+    let ops = vec![
+        Op::Add { task: "other task".to_string(), section: None },
+        Op::Rm { pattern: "missing".to_string(), section: None },
+    ];
+    let res = ureq::post(&url).send_json(Batch { ops });
+    let Err(ureq::Error::Status(404, res)) = res else {
+        panic!("expected the batch to fail");
+    };
+    let problem: Problem = res.into_json()?;
+    assert_eq!(problem.detail, "operation 2 failed: no task matching missing found");
+    assert_eq!(fs::read_to_string(&file_path)?, content);
+    Ok(())
+}
+
+#[test]
+fn it_pages_tasks() -> Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new()?;
+    let file_path = tmp_dir.path().join("api.md");
+    fs::write(&file_path, "## todo\n\n- first\n- second\n\n## dump\n\n- third\n")?;
+    let server = Server::start(&file_path)?;
+
+    let url = format!("{}/tasks/todo", server.url);
+    let page: ListResponse<Value> =
+        ureq::get(&url).query("limit", "1").query("fields", "text").call()?.into_json()?;
+    assert_eq!(page.items, vec![json!({"text": "first"})]);
+    assert_eq!(page.total, 2);
+    let cursor = page.next_cursor.unwrap();
+    let page: ListResponse<Task> =
+        ureq::get(&url).query("limit", "1").query("cursor", &cursor).call()?.into_json()?;
+    assert_eq!(page.items[0].text, "second");
+    assert_eq!(page.next_cursor, None);
+
+    let res = ureq::get(&format!("{}/tasks", server.url)).query("limit", "2").call()?;
+    assert_eq!(res.header(TOTAL_COUNT_HEADER), Some("3"));
+    let cursor = res.header(NEXT_CURSOR_HEADER).unwrap().to_string();
+    let sections: Value = res.into_json()?;
+    assert_eq!(sections.as_object().unwrap().keys().collect::<Vec<_>>(), vec!["todo"]);
+    // the cursor points after the second task, even if the first one is gone
+    ureq::delete(&format!("{}/tasks/todo", server.url)).query("pattern", "first").call()?;
+    let res = ureq::get(&format!("{}/tasks", server.url)).query("cursor", &cursor).call()?;
+    assert_eq!(res.header(NEXT_CURSOR_HEADER), None);
+    let sections: Value = res.into_json()?;
+    assert_eq!(sections["dump"][0]["text"], "third");
+    ureq::delete(&format!("{}/tasks/todo", server.url)).query("pattern", "second").call()?;
+    let res = ureq::get(&format!("{}/tasks", server.url)).query("cursor", &cursor).call();
+    assert!(matches!(res, Err(ureq::Error::Status(400, _))), "expected the cursor to be outdated");
+
+    for (key, value) in [("fields", "title"), ("cursor", "x"), ("limit", "0")] {
+        let res = ureq::get(&url).query(key, value).call();
+        assert!(matches!(res, Err(ureq::Error::Status(400, _))), "{key}={value}");
+    }
+    Ok(())
+}
+
+#[test]
+fn it_authorizes_requests() -> Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new()?;
+    let file_path = tmp_dir.path().join("api.md");
+    fs::write(&file_path, "## dump\n\n- update deps\n")?;
+    let tokens_path = tmp_dir.path().join("tokens");
+    fs::write(&tokens_path, "# ci\nwrite:tobi=write-token\n")?;
+    let server = Server::start_with_env(
+        &file_path,
+        &[
+            ("FRIDAY_TOKENS", "read=read-token"),
+            ("FRIDAY_TOKENS_FILE", tokens_path.to_str().unwrap()),
+        ],
+    )?;
+    let url = format!("{}/tasks", server.url);
+    let task = CreateTask { task: "new task".to_string() };
+
+    ureq::get(&format!("{}/api.json", server.url)).call()?;
+    for auth in [None, Some("Bearer wrong-token"), Some("read-token")] {
+        let mut req = ureq::get(&url);
+        if let Some(auth) = auth {
+            req = req.set("Authorization", auth);
+        }
+        let Err(ureq::Error::Status(401, res)) = req.call() else {
+            panic!("expected {auth:?} to be unauthorized");
+        };
+        assert_eq!(res.header("WWW-Authenticate"), Some("Bearer"));
+        let problem: Problem = res.into_json()?;
+        assert_eq!(problem.kind, "urn:friday:problem:unauthorized");
+    }
+
+    ureq::get(&url).set("Authorization", "Bearer read-token").call()?;
+    let res = ureq::post(&url).set("Authorization", "Bearer read-token").send_json(&task);
+    let Err(ureq::Error::Status(403, res)) = res else {
+        panic!("expected the read token to be forbidden");
+    };
+    let problem: Problem = res.into_json()?;
+    assert_eq!(problem.kind, "urn:friday:problem:forbidden");
+    assert!(!fs::read_to_string(&file_path)?.contains("new task"));
+
+    ureq::get(&url).set("Authorization", "Bearer write-token").call()?;
+    let res = ureq::post(&url).set("Authorization", "Bearer write-token").send_json(&task)?;
+    assert_eq!(res.status(), 201);
+    let created: Task = res.into_json()?;
+    assert_eq!(created.author.as_deref(), Some("tobi"));
+    Ok(())
+}
+
+#[test]
+fn it_refuses_to_start_without_tokens() -> Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new()?;
+    let cmd = Command::cargo_bin("fridaypi")?
+        .env("FRIDAY_FILE", tmp_dir.path().join("api.md"))
+        .env("FRIDAY_ADDR", "127.0.0.1:0")
+        .env_remove("FRIDAY_AUTH")
+        .env_remove("FRIDAY_TOKENS")
+        .timeout(Duration::from_secs(10))
+        .assert()
+        .failure();
+    let output_str = String::from_utf8_lossy(&cmd.get_output().stderr).to_string();
+    assert!(output_str.contains("set FRIDAY_AUTH=off"), "got '{output_str}'");
+    Ok(())
+}
+
+#[test]
+fn it_limits_requests() -> Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new()?;
+    let file_path = tmp_dir.path().join("api.md");
+    fs::write(&file_path, "## dump\n")?;
+    let server = Server::start_with_env(
+        &file_path,
+        &[
+            ("FRIDAY_AUTH", "off"),
+            ("FRIDAY_RATE_LIMIT", "3"),
+            ("FRIDAY_MAX_BODY_SIZE", "100"),
+            ("FRIDAY_MAX_TASK_LENGTH", "10"),
+        ],
+    )?;
+    let url = format!("{}/tasks", server.url);
+
+    for task in ["a".repeat(11), "a".repeat(200)] {
+        let Err(ureq::Error::Status(413, res)) = ureq::post(&url).send_json(CreateTask { task })
+        else {
+            panic!("expected the task to be too large");
+        };
+        let problem: Problem = res.into_json()?;
+        assert_eq!(problem.kind, "urn:friday:problem:too-large");
+    }
+    assert_eq!(fs::read_to_string(&file_path)?, "## dump\n");
+
+    ureq::get(&url).call()?;
+    let Err(ureq::Error::Status(429, res)) = ureq::get(&url).call() else {
+        panic!("expected the rate limit to be exceeded");
+    };
+    assert!(res.header("Retry-After").is_some());
+    Ok(())
+}
+
+#[test]
+fn it_cancels_requests_that_time_out() -> Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new()?;
+    let file_path = tmp_dir.path().join("api.md");
+    fs::write(&file_path, "## dump\n")?;
+    let server = Server::start_with_env(
+        &file_path,
+        &[("FRIDAY_AUTH", "off"), ("FRIDAY_REQUEST_TIMEOUT", "1")],
+    )?;
+
+    // the body is sent slower than the timeout allows
+    let body = r#"{"task": "slow task"}"#;
+    let (start, rest) = body.split_at(10);
+    let mut stream = TcpStream::connect(server.url.trim_start_matches("http://"))?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    write!(
+        stream,
+        "POST /tasks HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\
+         Content-Type: application/json\r\nContent-Length: {}\r\n\r\n{start}",
+        body.len()
+    )?;
+    thread::sleep(Duration::from_millis(1500));
+    let _ = stream.write_all(rest.as_bytes());
+
+    let mut res = String::new();
+    let _ = stream.read_to_string(&mut res);
+    assert!(res.starts_with("HTTP/1.1 408"), "got '{res}'");
+    thread::sleep(Duration::from_millis(500));
+    assert_eq!(fs::read_to_string(&file_path)?, "## dump\n");
+    Ok(())
+}
+
+#[test]
+fn it_serves_probes_and_metrics() -> Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new()?;
+    let file_path = tmp_dir.path().join("api.md");
+    fs::write(&file_path, "## todo\n\n- first\n- second\n\n## dump\n\n- third\n")?;
+    let server = Server::start_with_env(&file_path, &[("FRIDAY_TOKENS", "read=secret")])?;
+
+    // the probes can be used without a token
+    ureq::get(&format!("{}/healthz", server.url)).call()?;
+    ureq::get(&format!("{}/readyz", server.url)).call()?;
+    ureq::get(&format!("{}/tasks/todo", server.url))
+        .set("Authorization", "Bearer secret")
+        .call()?;
+
+    // the metrics need a token though
+    let res = ureq::get(&format!("{}/metrics", server.url)).call();
+    assert!(matches!(res, Err(ureq::Error::Status(401, _))));
+    let metrics = ureq::get(&format!("{}/metrics", server.url))
+        .set("Authorization", "Bearer secret")
+        .call()?
+        .into_string()?;
+    for line in [
+        "friday_tasks{list=\"default\",section=\"todo\"} 2",
+        "friday_tasks{list=\"default\",section=\"dump\"} 1",
+        "friday_http_requests_total{method=\"GET\",route=\"/tasks/:section\",status=\"200\"} 1",
+        "friday_http_request_duration_seconds_count{route=\"/readyz\"} 1",
+    ] {
+        assert!(metrics.lines().any(|l| l == line), "expected {line} in\n{metrics}");
+    }
+    assert!(metrics.contains("friday_repo_operation_duration_seconds_count{operation=\"list\"}"));
+
+    fs::remove_file(&file_path)?;
+    let res = ureq::get(&format!("{}/readyz", server.url)).call();
+    assert!(matches!(res, Err(ureq::Error::Status(503, _))));
+    ureq::get(&format!("{}/healthz", server.url)).call()?;
+    Ok(())
+}
+
+#[test]
+fn it_sets_request_ids() -> Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new()?;
+    let file_path = tmp_dir.path().join("api.md");
+    fs::write(&file_path, "## todo\n\n- first\n")?;
+    let server = Server::start_with_env(
+        &file_path,
+        &[
+            ("FRIDAY_AUTH", "off"),
+            ("FRIDAY_LOG_FORMAT", "json"),
+            ("FRIDAY_LOG", "info,friday_core=debug"),
+        ],
+    )?;
+
+    let res = ureq::get(&format!("{}/tasks", server.url)).call()?;
+    let generated = res.header("x-request-id").expect("request id should be generated");
+    assert!(!generated.is_empty());
+    let other = ureq::get(&format!("{}/tasks", server.url)).call()?;
+    assert_ne!(other.header("x-request-id"), Some(generated));
+
+    // ids of clients are kept, also for errors
+    let res = ureq::get(&format!("{}/tasks/todo/unknown", server.url))
+        .set("X-Request-Id", "client-id")
+        .call();
+    let Err(ureq::Error::Status(404, res)) = res else { panic!("expected 404, got {res:?}") };
+    assert_eq!(res.header("x-request-id"), Some("client-id"));
+    Ok(())
+}
@@ -0,0 +1,196 @@
+mod common;
+
+use std::{error::Error, fs};
+
+use common::Server;
+use friday_client::{Batch, CreateTask, ListResponse};
+use friday_core::{Op, Task};
+use serde_json::{json, Value};
+use tempfile::TempDir;
+
+// validate checks that the value matches the schema, resolving references
+// against the spec. Only the keywords schemars generates are supported.
+fn validate(spec: &Value, schema: &Value, value: &Value, at: &str) -> Result<(), String> {
+    let schema = match schema {
+        Value::Bool(true) => return Ok(()),
+        Value::Object(schema) => schema,
+        schema => return Err(format!("{at}: unsupported schema {schema}")),
+    };
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let Some(target) = spec.pointer(reference.trim_start_matches('#')) else {
+            return Err(format!("{at}: unresolved reference {reference}"));
+        };
+        return validate(spec, target, value, at);
+    }
+    if value.is_null() && schema.get("nullable") == Some(&Value::Bool(true)) {
+        return Ok(());
+    }
+    for schema in schema.get("allOf").and_then(Value::as_array).into_iter().flatten() {
+        validate(spec, schema, value, at)?;
+    }
+    for key in ["anyOf", "oneOf"] {
+        if let Some(schemas) = schema.get(key).and_then(Value::as_array) {
+            let matching = schemas.iter().filter(|s| validate(spec, s, value, at).is_ok()).count();
+            if matching == 0 || (key == "oneOf" && matching > 1) {
+                return Err(format!("{at}: {value} matches {matching} of {key}"));
+            }
+        }
+    }
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        if !values.contains(value) {
+            return Err(format!("{at}: {value} isn't one of {values:?}"));
+        }
+    }
+    if let Some(types) = schema.get("type") {
+        let types: Vec<&str> = match types {
+            Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+            types => types.as_str().into_iter().collect(),
+        };
+        if !types.iter().any(|t| has_type(value, t)) {
+            return Err(format!("{at}: {value} isn't of type {types:?}"));
+        }
+    }
+
+    if let Value::Object(object) = value {
+        let properties = schema.get("properties").and_then(Value::as_object);
+        for required in schema.get("required").and_then(Value::as_array).into_iter().flatten() {
+            let required = required.as_str().unwrap_or_default();
+            if !object.contains_key(required) {
+                return Err(format!("{at}: missing required {required}"));
+            }
+        }
+        for (key, value) in object {
+            let at = format!("{at}.{key}");
+            match (properties.and_then(|p| p.get(key)), schema.get("additionalProperties")) {
+                (Some(schema), _) | (None, Some(schema)) => validate(spec, schema, value, &at)?,
+                // schemars only restricts properties explicitly
+                (None, None) => {}
+            }
+        }
+    }
+    if let (Value::Array(values), Some(items)) = (value, schema.get("items")) {
+        for (i, value) in values.iter().enumerate() {
+            validate(spec, items, value, &format!("{at}[{i}]"))?;
+        }
+    }
+    Ok(())
+}
+
+fn has_type(value: &Value, kind: &str) -> bool {
+    match kind {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "string" => value.is_string(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => false,
+    }
+}
+
+// assert_documented fails unless the spec documents the status of the
+// response for the route and its body matches the documented schema.
+fn assert_documented(
+    spec: &Value,
+    route: (&str, &str),
+    res: Result<ureq::Response, ureq::Error>,
+) -> Result<(), Box<dyn Error>> {
+    let (method, path) = route;
+    let res = match res {
+        Ok(res) | Err(ureq::Error::Status(_, res)) => res,
+        Err(e) => return Err(e.into()),
+    };
+    let status = res.status().to_string();
+    let body: Value = res.into_json()?;
+
+    let documented = &spec["paths"][path][method]["responses"][&status]["content"];
+    let Some((_, content)) = documented.as_object().and_then(|c| c.iter().next()) else {
+        panic!("{method} {path} doesn't document a {status} response");
+    };
+    if let Err(e) = validate(spec, &content["schema"], &body, "$") {
+        panic!("{method} {path} responded with an undocumented {status} body: {e}");
+    }
+    Ok(())
+}
+
+#[test]
+fn it_responds_as_documented() -> Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new()?;
+    let file_path = tmp_dir.path().join("friday.md");
+    fs::write(&file_path, "## todo\n\n- update deps due:2026-10-23 #infra\n- review deps PR\n")?;
+    let server = Server::start(&file_path)?;
+    let url = |path: &str| format!("{}{path}", server.url);
+    let spec: Value = ureq::get(&url("/api.json")).call()?.into_json()?;
+
+    let page: ListResponse<Task> = ureq::get(&url("/tasks/todo")).call()?.into_json()?;
+    let task = format!("/tasks/todo/{}", page.items[0].id);
+    let checks = [
+        (("get", "/tasks"), ureq::get(&url("/tasks")).call()),
+        (("get", "/tasks"), ureq::get(&url("/tasks")).query("fields", "text").call()),
+        (("get", "/tasks"), ureq::get(&url("/tasks")).query("limit", "0").call()),
+        (("get", "/tasks/{section}"), ureq::get(&url("/tasks/todo")).call()),
+        (
+            ("get", "/tasks/{section}"),
+            ureq::get(&url("/tasks/todo")).query("limit", "1").query("fields", "id,text").call(),
+        ),
+        (("get", "/tasks/{section}"), ureq::get(&url("/tasks/done")).call()),
+        (("get", "/tasks/{section}/{id}"), ureq::get(&url(&task)).call()),
+        (("get", "/tasks/{section}/{id}"), ureq::get(&url("/tasks/todo/00000000")).call()),
+        (
+            ("post", "/tasks"),
+            ureq::post(&url("/tasks")).send_json(CreateTask { task: "new task".to_string() }),
+        ),
+        (
+            ("post", "/tasks"),
+            ureq::post(&url("/tasks")).send_json(CreateTask { task: " ".to_string() }),
+        ),
+        (
+            ("delete", "/tasks/{section}"),
+            ureq::delete(&url("/tasks/todo")).query("pattern", "deps").call(),
+        ),
+        (
+            ("post", "/tasks:batch"),
+            ureq::post(&url("/tasks:batch")).send_json(Batch {
+                ops: vec![Op::Add { task: "batched task".to_string(), section: None }],
+            }),
+        ),
+        (
+            ("post", "/tasks:batch"),
+            ureq::post(&url("/tasks:batch")).send_json(Batch {
+                ops: vec![Op::Rm { pattern: "missing".to_string(), section: None }],
+            }),
+        ),
+        (("get", "/search"), ureq::get(&url("/search")).query("q", "deps").call()),
+        (("get", "/lists"), ureq::get(&url("/lists")).call()),
+        (("get", "/lists/{list}/tasks"), ureq::get(&url("/lists/default/tasks")).call()),
+        (("get", "/lists/{list}/tasks"), ureq::get(&url("/lists/team/tasks")).call()),
+        (
+            ("get", "/lists/{list}/search"),
+            ureq::get(&url("/lists/default/search")).query("q", "deps").call(),
+        ),
+    ];
+    for (route, res) in checks {
+        assert_documented(&spec, route, res)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn it_detects_undocumented_responses() {
+    let spec = json!({
+        "components": {"schemas": {"Task": {
+            "type": "object",
+            "required": ["text"],
+            "properties": {"text": {"type": "string"}, "due": {"type": ["string", "null"]}},
+        }}},
+    });
+    let schema = json!({"type": "array", "items": {"$ref": "#/components/schemas/Task"}});
+
+    let valid = json!([{"text": "task", "due": null}]);
+    assert_eq!(validate(&spec, &schema, &valid, "$"), Ok(()));
+    let missing = json!([{"due": "2026-10-23"}]);
+    assert_eq!(validate(&spec, &schema, &missing, "$"), Err("$[0]: missing required text".into()));
+    let mistyped = json!([{"text": 1}]);
+    assert!(validate(&spec, &schema, &mistyped, "$").is_err());
+}
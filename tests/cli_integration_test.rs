@@ -39,3 +39,186 @@ fn it_adds_to_file() -> Result<(), Box<dyn Error>> {
     // assert that file contains string
     Ok(())
 }
+
+#[test]
+fn it_completes_recurring_tasks() -> Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new()?;
+    let file_path = tmp_dir.path().join("friday.md");
+    friday_cli()
+        .args(["add", "update deps every:week"])
+        .env("FRIDAY_FILE", &file_path)
+        .assert()
+        .success();
+    friday_cli().args(["done", "deps"]).env("FRIDAY_FILE", &file_path).assert().success();
+
+    let content = fs::read_to_string(&file_path)?;
+    assert!(
+        content.contains("- [x] update deps every:week <!-- "),
+        "expected '{}' to contain the completed task",
+        content
+    );
+    assert!(
+        content.contains("- update deps every:week due:"),
+        "expected '{}' to contain the next instance",
+        content
+    );
+    Ok(())
+}
+
+#[test]
+fn it_starts_a_new_week() -> Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new()?;
+    let file_path = tmp_dir.path().join("friday.md");
+    fs::write(&file_path, "# It's friday my dudes\n\n## dump\n- [x] done task\n- open task\n")?;
+    friday_cli().arg("new-week").env("FRIDAY_FILE", &file_path).assert().success();
+
+    let content = fs::read_to_string(&file_path)?;
+    assert_eq!(content, "# It's friday my dudes\n\n## dump\n- open task\n");
+    let archives: Vec<_> = fs::read_dir(tmp_dir.path().join("friday-archive"))?.collect();
+    assert_eq!(archives.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn it_prints_completions() {
+    let tmp_dir = TempDir::new().unwrap();
+    let cmd = friday_cli()
+        .args(["completions", "bash"])
+        .env("FRIDAY_FILE", tmp_dir.path().join("sth"))
+        .assert()
+        .success();
+    let output_str = String::from_utf8_lossy(&cmd.get_output().stdout).to_string();
+    assert!(output_str.contains("new-week"), "expected '{}' to contain subcommands", output_str);
+}
+
+#[test]
+fn it_shows_tasks_as_json() -> Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new()?;
+    let file_path = tmp_dir.path().join("friday.md");
+    fs::write(&file_path, "## dump\n- some #infra task\n")?;
+    let cmd = friday_cli()
+        .args(["show", "--format", "json"])
+        .env("FRIDAY_FILE", &file_path)
+        .assert()
+        .success();
+    let output_str = String::from_utf8_lossy(&cmd.get_output().stdout).to_string();
+    assert!(
+        output_str.contains(r#""dump": ["#),
+        "expected '{}' to contain the section",
+        output_str
+    );
+    assert!(
+        output_str.contains(r#""text": "some #infra task""#),
+        "expected '{}' to contain the task",
+        output_str
+    );
+    Ok(())
+}
+
+#[test]
+fn it_finds_tasks() -> Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new()?;
+    let file_path = tmp_dir.path().join("friday.md");
+    fs::write(&file_path, "## dump\n- do everything please\n- other\n## todo\n- update deps\n")?;
+    let cmd = friday_cli().args(["find", "deps"]).env("FRIDAY_FILE", &file_path).assert().success();
+    let output_str = String::from_utf8_lossy(&cmd.get_output().stdout).to_string();
+    let lines: Vec<&str> = output_str.lines().collect();
+    assert_eq!(lines.len(), 2, "expected '{output_str}' to contain two matches");
+    assert!(lines[0].contains("todo:"), "expected the best match first in '{output_str}'");
+    assert!(lines[1].contains("dump:"), "expected the worse match last in '{output_str}'");
+    Ok(())
+}
+
+#[test]
+fn it_prints_resolved_config() -> Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new()?;
+    let config_path = tmp_dir.path().join("config.toml");
+    fs::write(&config_path, "section = \"todo\"\nformat = \"plain\"\n")?;
+    let cmd = friday_cli()
+        .arg("config")
+        .env("FRIDAY_CONFIG", &config_path)
+        .env("FRIDAY_FILE", tmp_dir.path().join("friday.md"))
+        .env("FRIDAY_FORMAT", "json")
+        .assert()
+        .success();
+    let output_str = String::from_utf8_lossy(&cmd.get_output().stdout).to_string();
+    for expected in ["section = \"todo\"", "format = \"json\"", "friday.md"] {
+        assert!(output_str.contains(expected), "expected '{output_str}' to contain '{expected}'");
+    }
+    Ok(())
+}
+
+#[test]
+fn it_adds_to_named_list() -> Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new()?;
+    let config_path = tmp_dir.path().join("config.toml");
+    let team_path = tmp_dir.path().join("team.md");
+    fs::write(&config_path, format!("[lists]\nteam = {:?}\n", team_path.to_string_lossy()))?;
+    friday_cli()
+        .args(["-l", "team", "add", "plan", "the", "sprint"])
+        .env("FRIDAY_CONFIG", &config_path)
+        .env("FRIDAY_FILE", tmp_dir.path().join("friday.md"))
+        .env("USER", "tobi")
+        .assert()
+        .success();
+    let content = fs::read_to_string(&team_path)?;
+    assert!(content.contains("- plan the sprint <!-- author:tobi created:"), "got '{content}'");
+    assert!(!tmp_dir.path().join("friday.md").exists());
+
+    let cmd = friday_cli()
+        .arg("lists")
+        .env("FRIDAY_CONFIG", &config_path)
+        .env("FRIDAY_LIST", "team")
+        .assert()
+        .success();
+    let output_str = String::from_utf8_lossy(&cmd.get_output().stdout).to_string();
+    assert!(output_str.contains("* team"), "expected '{output_str}' to mark the team list");
+    Ok(())
+}
+
+#[test]
+fn it_exits_with_error_codes() -> Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new()?;
+    let file_path = tmp_dir.path().join("friday.md");
+    fs::write(&file_path, "## dump\n\n- update deps\n- review deps PR\n")?;
+
+    friday_cli().args(["add", " "]).env("FRIDAY_FILE", &file_path).assert().code(2);
+    friday_cli().args(["rm", "missing"]).env("FRIDAY_FILE", &file_path).assert().code(3);
+    let cmd = friday_cli().args(["rm", "deps"]).env("FRIDAY_FILE", &file_path).assert().code(4);
+    let output_str = String::from_utf8_lossy(&cmd.get_output().stderr).to_string();
+    assert!(output_str.contains("  1) update deps (dump, id "), "got '{output_str}'");
+    assert!(output_str.contains("  2) review deps PR (dump, id "), "got '{output_str}'");
+
+    // the id of a candidate selects it
+    let id = output_str.split("id ").nth(1).unwrap().split(')').next().unwrap();
+    friday_cli().args(["rm", id]).env("FRIDAY_FILE", &file_path).assert().success();
+    assert_eq!(fs::read_to_string(&file_path)?, "## dump\n\n- review deps PR\n");
+    Ok(())
+}
+
+#[test]
+fn it_runs_batches_atomically() -> Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new()?;
+    let file_path = tmp_dir.path().join("friday.md");
+    fs::write(&file_path, "## todo\n\n## dump\n\n- update deps\n- review PR\n")?;
+
+    friday_cli()
+        .arg("batch")
+        .env("FRIDAY_FILE", &file_path)
+        .write_stdin("add new task\nmv deps --to todo\n\n# comments are skipped\ndone review\n")
+        .assert()
+        .success();
+    let content = fs::read_to_string(&file_path)?;
+    let expected = "## todo\n- update deps\n\n## dump\n\n- [x] review PR\n- new task <!-- ";
+    assert!(content.starts_with(expected), "got '{content}'");
+
+    // nothing is applied if one of the commands fails
+    friday_cli()
+        .arg("batch")
+        .env("FRIDAY_FILE", &file_path)
+        .write_stdin("add other task\nrm missing\n")
+        .assert()
+        .code(3);
+    assert_eq!(fs::read_to_string(&file_path)?, content);
+    Ok(())
+}
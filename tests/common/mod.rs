@@ -0,0 +1,71 @@
+// not every test uses all helpers
+#![allow(dead_code)]
+
+// helpers shared by the integration tests, see
+// https://doc.rust-lang.org/book/ch11-03-test-organization.html#submodules-in-integration-tests
+use std::{
+    error::Error,
+    net::{SocketAddr, TcpListener, TcpStream},
+    path::Path,
+    process::{Child, Command as StdCommand},
+    thread,
+    time::Duration,
+};
+
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+// Server is a `fridaypi` process serving the given file on a free port, it's
+// killed when dropped.
+pub struct Server {
+    child: Child,
+    pub url: String,
+}
+
+impl Server {
+    // start starts the server without authentication.
+    pub fn start(file: &Path) -> Result<Server, Box<dyn Error>> {
+        Server::start_with_env(file, &[("FRIDAY_AUTH", "off")])
+    }
+
+    // start_with_env starts the server with additional env vars, e.g. to
+    // configure tokens.
+    pub fn start_with_env(file: &Path, vars: &[(&str, &str)]) -> Result<Server, Box<dyn Error>> {
+        // the port is free again as soon as the listener is dropped
+        let addr = TcpListener::bind("127.0.0.1:0")?.local_addr()?;
+        let child = StdCommand::new(env!("CARGO_BIN_EXE_fridaypi"))
+            .env("FRIDAY_FILE", file)
+            .env("FRIDAY_ADDR", addr.to_string())
+            .envs(vars.iter().copied())
+            .spawn()?;
+        let server = Server { child, url: format!("http://{addr}") };
+        wait_for(addr)?;
+        Ok(server)
+    }
+
+    pub fn friday_cli(&self, tmp_dir: &TempDir) -> Command {
+        let mut cmd = Command::cargo_bin("friday").unwrap();
+        cmd.env("FRIDAY_REMOTE", &self.url)
+            .env("FRIDAY_CONFIG", tmp_dir.path().join("config.toml"))
+            .env("FRIDAY_FILE", tmp_dir.path().join("local.md"));
+        cmd
+    }
+}
+
+// wait_for waits until something listens on the address.
+pub fn wait_for(addr: SocketAddr) -> Result<(), Box<dyn Error>> {
+    for _ in 0..100 {
+        if TcpStream::connect(addr).is_ok() {
+            return Ok(());
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+    Err(format!("nothing started listening on {addr}").into())
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
@@ -0,0 +1,59 @@
+mod common;
+
+use std::{error::Error, fs};
+
+use common::Server;
+use tempfile::TempDir;
+
+#[test]
+fn it_manages_remote_tasks() -> Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new()?;
+    let file_path = tmp_dir.path().join("remote.md");
+    fs::write(&file_path, "## todo\n\n- existing #infra task\n")?;
+    let server = Server::start(&file_path)?;
+
+    server.friday_cli(&tmp_dir).args(["add", "-s", "todo", "remote", "task"]).assert().success();
+    assert!(fs::read_to_string(&file_path)?.contains("- remote task <!-- created:"));
+
+    let cmd = server
+        .friday_cli(&tmp_dir)
+        .args(["show", "--format", "plain", "--tag", "infra"])
+        .assert()
+        .success();
+    let output_str = String::from_utf8_lossy(&cmd.get_output().stdout).to_string();
+    assert_eq!(output_str, "todo\texisting #infra task\n");
+
+    server.friday_cli(&tmp_dir).args(["rm", "-s", "todo", "existing"]).assert().success();
+    assert!(!fs::read_to_string(&file_path)?.contains("existing"));
+
+    server.friday_cli(&tmp_dir).args(["done", "-s", "todo", "remote"]).assert().success();
+    assert!(fs::read_to_string(&file_path)?.contains("- [x] remote task <!-- created:"));
+
+    // errors of the server are reported like local ones
+    server.friday_cli(&tmp_dir).args(["rm", "-s", "todo", "missing"]).assert().code(3);
+    server.friday_cli(&tmp_dir).args(["done", "remote"]).assert().code(3);
+    server.friday_cli(&tmp_dir).args(["add", " "]).assert().code(2);
+
+    assert!(!tmp_dir.path().join("local.md").exists());
+    Ok(())
+}
+
+#[test]
+fn it_sends_the_token() -> Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new()?;
+    let file_path = tmp_dir.path().join("remote.md");
+    fs::write(&file_path, "## dump\n")?;
+    let server = Server::start_with_env(&file_path, &[("FRIDAY_TOKENS", "write:tobi=secret")])?;
+
+    server.friday_cli(&tmp_dir).args(["add", "new", "task"]).assert().failure();
+    server
+        .friday_cli(&tmp_dir)
+        .env("FRIDAY_TOKEN", "secret")
+        .env("USER", "anna")
+        .args(["add", "new", "task"])
+        .assert()
+        .success();
+    // the author is the user of the token, not the local one
+    assert!(fs::read_to_string(&file_path)?.contains("- new task <!-- author:tobi created:"));
+    Ok(())
+}
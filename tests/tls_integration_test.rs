@@ -0,0 +1,58 @@
+mod common;
+
+use std::{error::Error, fs, net::TcpListener, sync::Arc};
+
+use common::Server;
+use friday_client::ListResponse;
+use friday_core::Task;
+use tempfile::TempDir;
+
+// agent returns a client that only trusts the certificate and doesn't follow
+// redirects.
+fn agent(cert: &rcgen::Certificate) -> Result<ureq::Agent, Box<dyn Error>> {
+    let mut roots = rustls::RootCertStore::empty();
+    roots.add(&rustls::Certificate(cert.serialize_der()?))?;
+    let config = rustls::ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    Ok(ureq::AgentBuilder::new().tls_config(Arc::new(config)).redirects(0).build())
+}
+
+#[test]
+fn it_serves_https() -> Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new()?;
+    let file_path = tmp_dir.path().join("tls.md");
+    fs::write(&file_path, "## dump\n\n- update deps\n")?;
+
+    // the certificate is generated for every run, so none has to be checked in
+    let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()])?;
+    let cert_path = tmp_dir.path().join("cert.pem");
+    fs::write(&cert_path, cert.serialize_pem()?)?;
+    let key_path = tmp_dir.path().join("key.pem");
+    fs::write(&key_path, cert.serialize_private_key_pem())?;
+    let redirect_addr = TcpListener::bind("127.0.0.1:0")?.local_addr()?;
+    let server = Server::start_with_env(
+        &file_path,
+        &[
+            ("FRIDAY_AUTH", "off"),
+            ("FRIDAY_TLS_CERT", cert_path.to_str().unwrap()),
+            ("FRIDAY_TLS_KEY", key_path.to_str().unwrap()),
+            ("FRIDAY_REDIRECT_ADDR", &redirect_addr.to_string()),
+        ],
+    )?;
+    let url = server.url.replace("http://127.0.0.1", "https://localhost");
+    let agent = agent(&cert)?;
+
+    let tasks: ListResponse<Task> = agent.get(&format!("{url}/tasks/dump")).call()?.into_json()?;
+    assert_eq!(tasks.items[0].text, "update deps");
+    // plain HTTP isn't served on the same port
+    assert!(ureq::get(&format!("{}/tasks", server.url)).call().is_err());
+
+    common::wait_for(redirect_addr)?;
+    let res =
+        agent.get(&format!("http://localhost:{}/tasks?tag=infra", redirect_addr.port())).call()?;
+    assert_eq!(res.status(), 308);
+    assert_eq!(res.header("Location"), Some(format!("{url}/tasks?tag=infra").as_str()));
+    Ok(())
+}